@@ -1,132 +1,167 @@
+// Generated by lyrn. sha256:35e0124f9d86c054
 const path = require('path');
-const webpack = require('webpack');
-const ForkTsCheckerWebpackPlugin = require('fork-ts-checker-webpack-plugin');
 const CopyWebpackPlugin = require('copy-webpack-plugin');
+const ForkTsCheckerWebpackPlugin = require('fork-ts-checker-webpack-plugin');
 const HtmlWebpackPlugin = require('html-webpack-plugin');
+const webpack = require('webpack');
 const cwd = process.cwd();
-const isDev = true;
 
 module.exports = {
-  cache: false,
-  devServer: {
-    compress: true,
-    headers: {
-      'Access-Control-Allow-Headers': 'X-Requested-With, content-type, Authorization',
-      'Access-Control-Allow-Methods': 'GET, POST, PUT, DELETE, PATCH, OPTIONS',
-      'Access-Control-Allow-Origin': '*'
-    },
-    historyApiFallback: true,
-    hot: true,
-    port: 8080,
-    static: './'
-  },
-  devtool: 'inline-source-map',
+  mode: 'development',
   entry: [
     path.resolve(cwd, 'src/main.ts')
   ],
-  infrastructureLogging: {
-    level: 'warn'
+  output: {
+    path: path.resolve(cwd, 'dist'),
+    publicPath: 'http://localhost:8080/',
+    filename: 'js/[name].[contenthash].bundle.js',
+    assetModuleFilename: 'assets/[hash][ext][query]'
+  },
+  resolve: {
+    modules: [
+      path.resolve(cwd, 'src'),
+      'node_modules'
+    ],
+    extensions: [
+      '.tsx',
+      '.ts',
+      '.mjs',
+      '.js',
+      '.jsx',
+      '.json',
+      '.wasm',
+      '.css',
+      '.vue'
+    ],
+    alias: {
+      build: path.resolve(cwd, 'dist'),
+      images: path.resolve(cwd, 'src/images'),
+      main: path.resolve(cwd, 'src/main.ts'),
+      public: path.resolve(cwd, 'public'),
+      src: path.resolve(cwd, 'src')
+    }
   },
-  mode: 'development',
   module: {
     rules: [
-      new Object({
+      {
         test: /\.tsx?$/,
         exclude: /(node_modules|\.webpack)/,
-        use: {
-          loader: 'ts-loader',
-          options: {
-            transpileOnly: true,
-          },
-        },
-      }),
-      new Object({
+        use: [
+          {
+            loader: 'ts-loader',
+            options: {
+              transpileOnly: true
+            }
+          }
+        ]
+      },
+      {
         test: /\.(sass|scss|css)$/,
         use: [
-          { loader: isDev ? 'style-loader' : MiniCssExtractPlugin.loader },
+          {
+            loader: 'style-loader'
+          },
           {
             loader: 'css-loader',
             options: {
-              importLoaders: isDev ? 1 : 2,
-              sourceMap: isDev,
-              modules: isDev,
-            },
+              importLoaders: 1,
+              sourceMap: true
+            }
           },
-          { loader: 'postcss-loader', options: { sourceMap: isDev } },
-          { loader: 'sass-loader', options: { sourceMap: isDev } },
-        ],
-      }),
-      new Object({
-        test: /\.(?:ico|gif|png|jpe?g)$/i,
-        type: 'asset/resource',
-        generator: {
-          filename: 'assets/[hash][ext][query]',
+          {
+            loader: 'postcss-loader',
+            options: {
+              sourceMap: true
+            }
+          },
+          {
+            loader: 'sass-loader',
+            options: {
+              sourceMap: true
+            }
+          }
+        ]
+      },
+      {
+        test: /\.(?:ico|gif|png|jpg|jpeg|webp|avif)$/i,
+        type: 'asset',
+        parser: {
+          dataUrlCondition: {
+            maxSize: 8192
+          }
         },
-      }),
-      new Object({
-        test: /\.(woff(2)?|eot|ttf|otf|svg|)$/i,
-        type: 'asset/inline',
-      })
-    ],
-  },
-  optimization: {
-    minimize: false
-  },
-  output: {
-    assetModuleFilename: 'assets/[hash][ext][query]',
-    filename: 'js/[name].[contenthash].bundle.js',
-    path: path.resolve(cwd, 'dist'),
-    publicPath: 'http://localhost:8080/'
-  },
-  performance: {
-    hints: false
+        generator: {
+          filename: 'assets/[hash][ext][query]'
+        }
+      },
+      {
+        test: /\.svg$/i,
+        type: 'asset',
+        parser: {
+          dataUrlCondition: {
+            maxSize: 8192
+          }
+        }
+      },
+      {
+        test: /\.(?:woff|woff2|eot|ttf|otf)$/i,
+        type: 'asset',
+        parser: {
+          dataUrlCondition: {
+            maxSize: 8192
+          }
+        }
+      }
+    ]
   },
   plugins: [
     new ForkTsCheckerWebpackPlugin(),
     new CopyWebpackPlugin({
-      patterns: [{
-        from: path.resolve(cwd, 'public'),
-        to: 'assets',
-        globOptions: {
-          ignore: ['*.DS_Store'],
-        },
-        noErrorOnMissing: true, 
-      }],
+      patterns: [
+        {
+          from: path.resolve(cwd, 'public'),
+          to: 'assets',
+          globOptions: {
+            ignore: [
+              '*.DS_Store'
+            ]
+          },
+          noErrorOnMissing: true
+        }
+      ]
     }),
     new HtmlWebpackPlugin({
-      title: 'APP TITLE',
-      favicon: path.resolve(cwd, './src/images/logo.svg'),
-      template: path.resolve(cwd, './src/index.html'),
-      filename: 'index.html'
+      filename: 'index.html',
+      template: path.resolve(cwd, 'src/index.html')
     }),
     new webpack.HotModuleReplacementPlugin()
   ],
-  resolve: {
-    alias: {
-      build: path.resolve(cwd, 'dist'),
-      images: path.resolve(cwd, 'src/images'),
-      main: path.resolve(cwd, 'src/main.ts'),
-      public: path.resolve(cwd, 'public'),
-      src: path.resolve(cwd, 'src')
-    },
-    extensions: [
-      '.tsx',
-      '.ts',
-      '.mjs',
-      '.js',
-      '.jsx',
-      '.json',
-      '.wasm',
-      '.css'
-    ],
-    modules: [
-      path.resolve(cwd, 'src'),
-      'node_modules'
-    ]
+  devtool: 'inline-source-map',
+  optimization: {
+    minimize: false
+  },
+  performance: {
+    hints: false
+  },
+  cache: false,
+  target: 'web',
+  devServer: {
+    historyApiFallback: true,
+    compress: true,
+    host: 'localhost',
+    port: 8080,
+    static: './',
+    headers: {
+      'Access-Control-Allow-Origin': '*',
+      'Access-Control-Allow-Methods': 'GET, POST, PUT, DELETE, PATCH, OPTIONS',
+      'Access-Control-Allow-Headers': 'X-Requested-With, content-type, Authorization'
+    }
+  },
+  infrastructureLogging: {
+    level: 'warn'
   },
   stats: {
     assets: false,
     modules: false
-  },
-  target: 'web'
-}
+  }
+};
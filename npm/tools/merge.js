@@ -0,0 +1,107 @@
+'use strict';
+
+// Applies a custom webpack configuration to the one generated by lyrn. Used by the lyrn bridge and
+// copied into ejected projects, so both merge the same way.
+const { merge, mergeWithCustomize } = require('webpack-merge');
+
+const NAME = '__lyrnName';
+const DEFAULT_MERGE = {
+  entry: 'replace',
+  plugins: 'replace',
+  'module.rules': 'replace',
+};
+
+// `names` lists the names of the generated rules and plugins in their order in the configuration.
+function applyNames(config, names) {
+  names.rules.forEach((name, index) => setName(config.module.rules[index], name));
+  names.plugins.forEach((name, index) => setName(config.plugins[index], name));
+  return config;
+}
+
+// A function receives the generated configuration and returns the final one, an object is merged
+// into it with the strategies of the `merge` section of lyrn.json.
+async function mergeConfig(config, custom, { mode, strategies = {} }) {
+  if (typeof custom === 'function') {
+    const find = (list, name) => list.find((item) => nameOf(item) === name);
+    const remove = (list, name) => list.filter((item) => nameOf(item) !== name);
+    return withoutNames((await custom(config, { mode, find, remove })) || config);
+  }
+  strategies = { ...DEFAULT_MERGE, ...strategies };
+  return withoutNames(mergeWithCustomize({
+    customizeArray(a, b, key) {
+      switch (strategies[key]) {
+        case 'append':
+          return [...a, ...b];
+        case 'prepend':
+          return [...b, ...a];
+        case 'replace':
+          return b;
+        case 'merge':
+          return mergeByName(a, b);
+      }
+    },
+    customizeObject(a, b, key) {
+      return strategies[key] === 'replace' ? b : undefined;
+    },
+  })(config, custom));
+}
+
+// Rules and plugins generated by lyrn carry a name, so overrides can patch or remove them.
+// Rules in an override are matched by their `name` property, plugins by `name` or by class.
+function setName(item, name) {
+  if (item) {
+    Object.defineProperty(item, NAME, { value: name, configurable: true });
+  }
+}
+
+function isPlainObject(value) {
+  return value !== null && typeof value === 'object' && Object.getPrototypeOf(value) === Object.prototype;
+}
+
+function nameOf(item) {
+  if (item === null || typeof item !== 'object') {
+    return undefined;
+  }
+  return item[NAME] || (isPlainObject(item) ? item.name : undefined);
+}
+
+function isSameItem(existing, item) {
+  const name = nameOf(item);
+  if (name) {
+    return nameOf(existing) === name;
+  }
+  return !isPlainObject(item) && !isPlainObject(existing) && existing.constructor === item.constructor;
+}
+
+function mergeByName(defaults, overrides) {
+  const result = [...defaults];
+  for (const item of overrides) {
+    const index = result.findIndex((existing) => isSameItem(existing, item));
+    if (isPlainObject(item) && item.remove) {
+      if (index !== -1) {
+        result.splice(index, 1);
+      }
+    } else if (index === -1) {
+      result.push(item);
+    } else if (isPlainObject(item) && isPlainObject(result[index])) {
+      const { name, ...patch } = item;
+      const patched = merge(result[index], patch);
+      setName(patched, name);
+      result[index] = patched;
+    } else {
+      result[index] = item;
+    }
+  }
+  return result;
+}
+
+function withoutNames(config) {
+  for (const rule of (config.module && config.module.rules) || []) {
+    if (isPlainObject(rule)) {
+      delete rule.name;
+    }
+  }
+  return config;
+}
+
+module.exports = { applyNames, mergeConfig, withoutNames };
@@ -0,0 +1,25 @@
+'use strict';
+
+// browserslist names of the browsers esbuild accepts as a target.
+const ESBUILD_BROWSERS = {
+  chrome: 'chrome',
+  and_chr: 'chrome',
+  edge: 'edge',
+  firefox: 'firefox',
+  and_ff: 'firefox',
+  safari: 'safari',
+  ios_saf: 'ios',
+  opera: 'opera',
+};
+
+// esbuild does not read browserslist: maps its result such as `ios_saf 16.4-16.5` to `ios16.4`.
+// Browsers esbuild does not know are left out.
+function esbuildTargets(browsers) {
+  return browsers.flatMap((browser) => {
+    const [name, version] = browser.split(' ');
+    const target = ESBUILD_BROWSERS[name];
+    return target ? [target + version.split('-')[0]] : [];
+  });
+}
+
+module.exports = { esbuildTargets };
@@ -2,84 +2,304 @@
 
 const webpack = require('webpack');
 const WebpackDevServer = require('webpack-dev-server');
-const { resolve } = require('path');
-const { mergeWithCustomize, customizeArray, } = require('webpack-merge');
+const { resolve, extname } = require('path');
+const { pathToFileURL } = require('url');
+const { applyNames, mergeConfig, withoutNames } = require('./merge');
 
-process.stdin.on('data', function (inputData) {
-  const { config, project_config, constants, plugins, rules } = JSON.parse(inputData);
+const PROTOCOL_VERSION = 1;
 
-  for (const constant of constants) {
-    eval(`global.${constant}`);
-  }
-  for (const rule of rules) {
-    config.module.rules.push(eval(rule));
-  }
-  for (const plugin of plugins) {
-    config.plugins.push(eval(plugin));
-  }
+let input = '';
+process.stdin.on('data', function (inputData) {
+  input += inputData;
+});
+process.stdin.on('end', function () {
+  const json = JSON.parse(input);
+  const { project_config } = json;
+  const config = prepare(json);
+  const legacy = json.legacy && prepare(json.legacy);
 
   switch (true) {
     case process.argv.includes('start'):
-      start({ config, project_config });
+      start({ config, project_config }).catch(fatal);
       break;
     case process.argv.includes('build'):
-      build({ config, project_config });
+      build({ config, legacy, project_config }).catch(fatal);
+      break;
+    case process.argv.includes('inspect'):
+      inspect({ config, project_config }).catch(fatal);
       break;
     default:
       process.exit(0);
   }
 });
 
-function start({ config, project_config }) {
-  config = getConfig(config, project_config.dev.config);
+process.on('uncaughtException', fatal);
+process.on('unhandledRejection', fatal);
+
+function send(type, payload = {}) {
+  process.stdout.write(JSON.stringify({ v: PROTOCOL_VERSION, type, ...payload }) + '\n');
+}
+
+function fatal(err) {
+  send('fatal', {
+    message: err && err.message ? err.message : String(err),
+    stack: err && err.stack ? err.stack : '',
+  });
+  process.exit(1);
+}
+
+function prepare(json) {
+  return applyNames(materialize(json.config, load(json.imports)), json.names);
+}
+
+// lyrn's own helpers are loaded from the package of the bridge, it is not always hoisted.
+function load(imports) {
+  const scope = {};
+  for (const { name, module, member } of imports) {
+    const exports = require(module.startsWith('lyrn/') ? resolve(__dirname, '..', module.slice('lyrn/'.length)) : module);
+    if (name) {
+      scope[name] = member ? exports[member] : exports;
+    }
+  }
+  return scope;
+}
+
+function reference(name, scope) {
+  const [root, ...members] = name.split('.');
+  if (!(root in scope)) {
+    throw new Error(`Unknown reference \`${name}\` in the webpack configuration`);
+  }
+  return members.reduce((value, member) => value[member], scope[root]);
+}
+
+// Rebuilds the configuration from the tagged JSON produced by lyrn: `$new`, `$call`, `$ref`,
+// `$regex` and `$require` nodes become values. Code is never evaluated, `$raw` nodes are rejected.
+function materialize(node, scope) {
+  if (Array.isArray(node)) {
+    return node.map((item) => materialize(item, scope));
+  }
+  if (node === null || typeof node !== 'object') {
+    return node;
+  }
+  if ('$new' in node) {
+    const Constructor = reference(node.$new, scope);
+    return new Constructor(...materialize(node.args, scope));
+  }
+  if ('$call' in node) {
+    const owner = node.$call.includes('.') ? reference(node.$call.replace(/\.[^.]+$/, ''), scope) : undefined;
+    return reference(node.$call, scope).apply(owner, materialize(node.args, scope));
+  }
+  if ('$ref' in node) {
+    return reference(node.$ref, scope);
+  }
+  if ('$regex' in node) {
+    return new RegExp(node.$regex, node.flags);
+  }
+  if ('$require' in node) {
+    return require(node.$require);
+  }
+  if ('$raw' in node) {
+    throw new Error('Raw JavaScript is not accepted in the webpack configuration sent to the bridge');
+  }
+  return Object.fromEntries(Object.entries(node).map(([key, value]) => [key, materialize(value, scope)]));
+}
+
+function progressPlugin() {
+  let lastPercent = -1;
+  return new webpack.ProgressPlugin((percentage, message) => {
+    const percent = Math.floor(percentage * 100);
+    if (percent === lastPercent) {
+      return;
+    }
+    lastPercent = percent;
+    send('progress', { percent, message });
+  });
+}
+
+function problems(list) {
+  return (list || []).map((problem) => {
+    const [, line, column] = /^(\d+):(\d+)/.exec(problem.loc || '') || [];
+    return {
+      message: problem.message,
+      file: problem.moduleName || problem.file || null,
+      line: line ? Number(line) : null,
+      column: column ? Number(column) : null,
+    };
+  });
+}
+
+function compilation(stats, statsOptions) {
+  const json = stats.toJson({ all: false, errors: true, warnings: true, timings: true, hash: true });
+  const payload = {
+    hash: json.hash,
+    duration: stats.endTime - stats.startTime,
+    errors: problems(json.errors),
+    warnings: problems(json.warnings),
+  };
+  if (statsOptions) {
+    payload.stats = stats.toJson(statsOptions);
+  }
+  const cache = stats.compilation.options.cache;
+  if (cache && cache.type === 'filesystem') {
+    const { modules } = stats.toJson({ all: false, modules: true, cachedModules: true, modulesSpace: Infinity });
+    const hits = (modules || []).filter((module) => module.cached).length;
+    payload.cache = { hits, misses: (modules || []).length - hits };
+  }
+  return payload;
+}
+
+async function start({ config, project_config }) {
+  config = await getConfig(config, project_config, 'development');
+  config.plugins.push(progressPlugin());
   const devServerOptions = config.devServer;
   const compiler = webpack(config);
   const server = new WebpackDevServer(devServerOptions, compiler);
 
   compiler.hooks.done.tap('serve', (stats) => {
-    if (stats.hasErrors()) {
-      return;
-    }
-    console.log('done');
+    send('done', compilation(stats));
   });
   compiler.hooks.compile.tap('serve', () => {
-    console.log('compile');
-  });
-  server.start(project_config.dev.port, `${project_config.dev.protocol}://${project_config.dev.host}`, (err) => {
-    if (err) {
-      process.exit(0);
-    }
+    send('compile');
   });
+  server.start().catch(fatal);
 }
 
-function build({ config, project_config }) {
-  config = getConfig(config, project_config.prod.config);
-  webpack(config, (err, stats) => {
-    if (err || stats.hasErrors()) {
-      process.exit(0);
-    }
+async function build({ config, legacy, project_config }) {
+  config = await getConfig(config, project_config, 'production');
+  let legacyStats;
+  if (legacy) {
+    legacy = await getConfig(legacy, project_config, 'production');
+    legacy.plugins.push(progressPlugin());
+    send('compile');
+    legacyStats = await run(legacy);
+    config.plugins.push(legacyScriptsPlugin(legacyStats));
+  }
+  config.plugins.push(progressPlugin());
+  send('compile');
+  const stats = await run(config);
 
-    console.log('done',
-      JSON.stringify(stats.toJson({
-        colors: true,
-        modules: false,
-        children: false,
-        chunks: false,
-        chunkModules: false,
-      })));
+  const analyze = process.argv.includes('--analyze');
+  const statsOptions = {
+    colors: true,
+    modules: analyze,
+    nestedModules: analyze,
+    reasons: analyze,
+    modulesSpace: Infinity,
+    nestedModulesSpace: Infinity,
+    children: false,
+    assets: true,
+    chunks: true,
+    chunkModules: false,
+    entrypoints: true,
+  };
+  const payload = compilation(stats, statsOptions);
+  if (legacyStats) {
+    const legacyPayload = compilation(legacyStats, { all: false, assets: true });
+    const assets = new Set(payload.stats.assets.map((asset) => asset.name));
+    payload.errors.push(...legacyPayload.errors);
+    payload.warnings.push(...legacyPayload.warnings);
+    payload.stats.assets.push(...legacyPayload.stats.assets.filter((asset) => !assets.has(asset.name)));
+  }
+  send('done', payload);
+}
+
+function run(config) {
+  return new Promise((resolve, reject) => {
+    webpack(config, (err, stats) => (err ? reject(err) : resolve(stats)));
   });
 }
 
-function getConfig(config, custom_config_path) {
+// Adds the scripts of the legacy build to the pages of the modern build. Browsers with ES modules
+// support skip `nomodule` scripts, older browsers skip the modern `type="module"` ones.
+function legacyScriptsPlugin(stats) {
+  const HtmlWebpackPlugin = require('html-webpack-plugin');
+  const { entrypoints } = stats.toJson({ all: false, entrypoints: true });
+  return {
+    apply(compiler) {
+      compiler.hooks.compilation.tap('LegacyScripts', (compilation) => {
+        HtmlWebpackPlugin.getHooks(compilation).alterAssetTags.tap('LegacyScripts', (data) => {
+          const { chunks } = data.plugin.options;
+          const names = Object.keys(entrypoints).filter((name) => chunks === 'all' || chunks.includes(name));
+          const files = new Set(names.flatMap((name) => entrypoints[name].assets.map((asset) => asset.name)));
+          for (const file of [...files].filter((file) => file.endsWith('.js'))) {
+            const src = data.publicPath + file;
+            data.assetTags.scripts.push(HtmlWebpackPlugin.createHtmlTagObject('script', { src, nomodule: true, defer: true }));
+          }
+          return data;
+        });
+      });
+    },
+  };
+}
+
+async function inspect({ config, project_config }) {
+  const mode = process.argv.includes('--prod') ? 'production' : 'development';
+  config = await getConfig(config, project_config, mode);
+  send('config', { config: describe(config, new Set()) });
+}
+
+// Turns the resolved configuration back into the tagged JSON lyrn prints as JavaScript:
+// regexes, functions and class instances become `$regex`, `$raw` and `$new` nodes.
+function describe(value, seen) {
+  if (value instanceof RegExp) {
+    return { $regex: value.source, flags: value.flags };
+  }
+  if (typeof value === 'function') {
+    const source = Function.prototype.toString.call(value);
+    return /^class\b/.test(source) && value.name ? { $ref: value.name } : { $raw: source };
+  }
+  if (typeof value === 'bigint' || typeof value === 'symbol') {
+    return { $raw: String(value) };
+  }
+  if (value === null || typeof value !== 'object') {
+    return value === undefined ? null : value;
+  }
+  if (seen.has(value)) {
+    return { $raw: '[Circular]' };
+  }
+  seen.add(value);
+  let result;
+  if (Array.isArray(value)) {
+    result = value.map((item) => describe(item, seen));
+  } else {
+    const entries = Object.entries(value).filter(([, item]) => item !== undefined);
+    const object = Object.fromEntries(entries.map(([key, item]) => [key, describe(item, seen)]));
+    const prototype = Object.getPrototypeOf(value);
+    const isInstance = prototype !== null && prototype !== Object.prototype;
+    result = isInstance ? { $new: value.constructor.name || 'Object', args: entries.length ? [object] : [] } : object;
+  }
+  seen.delete(value);
+  return result;
+}
+
+async function loadConfig(file) {
+  switch (extname(file)) {
+    case '.ts': {
+      try {
+        require.resolve('ts-node');
+      } catch {
+        throw new Error(`ts-node is required to load ${file}. Install it with \`npm i -D ts-node\``);
+      }
+      require('ts-node').register({
+        transpileOnly: true,
+        compilerOptions: { module: 'commonjs', importHelpers: false },
+      });
+      const exports = require(file);
+      return exports.default || exports;
+    }
+    case '.mjs':
+      return (await import(pathToFileURL(file).href)).default;
+    default:
+      return require(file);
+  }
+}
+
+async function getConfig(config, project_config, mode) {
+  const custom_config_path = mode === 'development' ? project_config.dev.config : project_config.prod.config;
   if (!custom_config_path) {
-    return config;
-  }
-  const custom_config = require(resolve(custom_config_path));
-  return mergeWithCustomize({
-    customizeArray: customizeArray({
-      entry: 'replace',
-      plugins: 'replace',
-      'module.rules': 'replace',
-    })
-  })(config, custom_config);
-}
\ No newline at end of file
+    return withoutNames(config);
+  }
+  const custom_config = await loadConfig(resolve(custom_config_path));
+  return mergeConfig(config, custom_config, { mode, strategies: project_config.merge });
+}
+
@@ -1,80 +1,330 @@
-use crate::libs::helpers::{clear_console, spinner_start, convert_bytes};
-use crate::libs::project_config::ProjectConfig;
+use crate::libs::analyzer::{self, Analysis};
+use crate::libs::baseline::{self, Baseline, Change, SizeDiff};
+use crate::libs::browserslist;
+use crate::libs::budgets::{self, BudgetCheck, BudgetStatus};
+use crate::libs::declarations;
+use crate::libs::helpers::{convert_bytes, format_delta, print_problems, remove_output_dir};
+use crate::libs::output::{self, Progress};
+use crate::libs::project_config::{ProjectConfig, Transpiler};
+use crate::libs::report::Report;
+use crate::libs::types::ExitError;
+use crate::tools::bridge::{self, Bridge, Compilation, Event, Problem};
+use crate::tools::js::Expr;
 use crate::tools::webpack;
-use clap::Args;
-use serde_json::{from_str, Value};
-use spinners::Spinner;
+use clap::{Args, ValueEnum};
 use std::error::Error;
 use std::fs;
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Command, Stdio};
-use std::thread;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Args)]
 #[command(args_conflicts_with_subcommands = true)]
 pub struct BuildArgs {
     script: Option<String>,
+    #[arg(long, help = "Treat warnings as errors")]
+    strict: bool,
+    #[arg(long, help = "Print the build report as JSON to stdout")]
+    json: bool,
+    #[arg(long, value_name = "FILE", help = "Write the build report as JSON to a file")]
+    report: Option<PathBuf>,
+    #[arg(long, help = "Save asset sizes as the baseline for later comparisons")]
+    save_baseline: bool,
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "table",
+        conflicts_with = "json",
+        help = "Compare asset sizes with the saved baseline"
+    )]
+    compare: Option<CompareFormat>,
+    #[arg(long, help = "Analyze the bundle and generate an HTML treemap report")]
+    analyze: bool,
+    #[arg(long, help = "Also build an ES5 bundle for browsers without ES modules support")]
+    legacy: bool,
 }
 
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CompareFormat {
+    Table,
+    Markdown,
+}
+
+impl BuildArgs {
+    pub fn writes_stdout(&self) -> bool {
+        self.json || self.compare == Some(CompareFormat::Markdown)
+    }
+}
+
+const EXIT_COMPILATION_ERRORS: i32 = 2;
+const EXIT_MISSING_MODULES: i32 = 3;
+const EXIT_NODE_CRASHED: i32 = 4;
+const EXIT_BUDGET_EXCEEDED: i32 = 5;
+
 pub fn cmd(build_args: BuildArgs) -> Result<(), Box<dyn Error>> {
-    let script = build_args.script.or(Some("node_modules/lyrn/tools/webpack.js".into())).unwrap();
-    let dist_dir = "dist";
+    let script = build_args.script.unwrap_or(bridge::SCRIPT.into());
+    let project_config = ProjectConfig::get(&None);
+    let dist_dir = &project_config.paths.output;
 
-    if let Err(_) = fs::metadata(&script) {
+    if fs::metadata(&script).is_err() {
         return Err(format!("File {} does not exist! Run the `build` command only in the project folder.", script).into());
     }
 
-    if fs::metadata(&dist_dir).is_ok() {
-        fs::remove_dir_all(&dist_dir)?;
-    }
+    remove_output_dir(dist_dir)?;
 
-    let mut child = Command::new("node")
-        .arg(&script)
-        .arg("build")
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("Failed to spawn child process");
+    browserslist::sync(&project_config)?;
+    declarations::sync(&project_config)?;
+    if build_args.legacy && project_config.transpiler == Transpiler::Ts {
+        output::warn("ts-loader only lowers the syntax of the project sources: the legacy bundle gets no polyfills and dependencies are not transpiled");
+        output::warn("Set `transpiler` to babel-loader or swc-loader in lyrn.json to support legacy browsers");
+    }
+    let webpack_config = match build_args.legacy {
+        true => webpack::get_config_legacy(&project_config),
+        false => webpack::get_config_prod(&project_config),
+    };
+    let mut bridge_args = vec!["build"];
+    if build_args.analyze {
+        bridge_args.push("--analyze");
+    }
+    let mut bridge = Bridge::spawn(&script, &bridge_args, &webpack_config)?;
+    let mut progress = Some(Progress::start("Loading..."));
+    let mut compilation: Option<Compilation> = None;
 
-    let mut child_stdin: std::process::ChildStdin = child.stdin.take().expect("Failed to open stdin for child process");
-    let mut spinner = spinner_start("Loading...").unwrap();
-    let project_config = ProjectConfig::get(&None);
-    let webpack_config = webpack::get_config_prod(true, &project_config);
-    let json_string = serde_json::to_string(&webpack_config).unwrap();
-    child_stdin.write_all(&json_string.as_bytes()).expect("Failed to write to child process stdin");
-    drop(child_stdin);
-    let stdout = child.stdout.expect("Failed to open stdout for child process");
-
-    let handle = thread::spawn(move || {
-        let reader = BufReader::new(stdout);
-        for line in reader.lines() {
-            match line.unwrap().as_str() {
-                done_str if done_str.starts_with("done") => done(&mut spinner, &done_str).unwrap(),
-                _ => (),
+    for event in bridge.events()? {
+        match event {
+            Event::Compile | Event::Config { .. } => (),
+            Event::Progress { percent, message } => {
+                if let Some(progress) = progress.as_mut() {
+                    progress.update(&format!("Loading... {}% {}", percent, message));
+                }
+            }
+            Event::Done(done) => {
+                if let Some(progress) = progress.take() {
+                    progress.stop();
+                }
+                compilation = Some(done);
+            }
+            Event::Fatal { message, stack } => {
+                drop(progress.take());
+                output::debug(&stack);
+                bridge.wait()?;
+                return Err(ExitError::new(EXIT_NODE_CRASHED, message).into());
             }
         }
-    });
-    handle.join().unwrap();
+    }
+    let status = bridge.wait()?;
+    drop(progress);
 
+    let Some(compilation) = compilation else {
+        return Err(ExitError::new(
+            EXIT_NODE_CRASHED,
+            format!("The Node process exited ({}) before the build was completed", status),
+        )
+        .into());
+    };
+    done(&compilation, build_args.strict)?;
+
+    if build_args.analyze {
+        analyze(&compilation)?;
+    }
+
+    let budgets = &project_config.budgets;
+    if !build_args.json && build_args.report.is_none() && !build_args.save_baseline && build_args.compare.is_none() && budgets.is_empty() {
+        return Ok(());
+    }
+
+    let mode = webpack_config.config.get("mode").and_then(Expr::as_str).unwrap_or("production");
+    let report = Report::new(&compilation, mode, Path::new(dist_dir));
+    let baseline = Baseline::load();
+    let checks = match budgets.is_empty() {
+        true => Vec::new(),
+        false => budgets::check(budgets, &report, baseline.as_ref())?,
+    };
+    print_budgets(&checks);
+
+    if let Some(format) = build_args.compare {
+        match &baseline {
+            Some(baseline) => print_comparison(baseline, &Baseline::from_report(&report), format),
+            None => output::warn("⚠️  There is no baseline to compare with. Run `lyrn build --save-baseline` first"),
+        }
+    }
+    if build_args.save_baseline {
+        Baseline::from_report(&report).save()?;
+        output::info(&format!("Baseline has been saved to {}", baseline::BASELINE_FILE));
+    }
+
+    if let Some(path) = &build_args.report {
+        report.write(path)?;
+        output::info(&format!("Build report has been written to {}", path.display()));
+    }
+    if build_args.json {
+        println!("{}", report.to_json()?);
+    }
+
+    let exceeded = checks.iter().filter(|check| check.status == BudgetStatus::Fail).count();
+    match exceeded {
+        0 => Ok(()),
+        _ => Err(ExitError::new(EXIT_BUDGET_EXCEEDED, format!("The build exceeded {} budget(s)", exceeded)).into()),
+    }
+}
+
+fn analyze(compilation: &Compilation) -> Result<(), Box<dyn Error>> {
+    let analysis = Analysis::new(&compilation.stats);
+    analysis.write_html(Path::new(analyzer::ANALYZER_FILE))?;
+    output::print("");
+    for line in analyzer::summary(&analysis, 10) {
+        output::print(&line);
+    }
+    output::print("");
+    output::info(&format!("Bundle analyzer report has been written to {}", analyzer::ANALYZER_FILE));
     Ok(())
 }
 
-fn done(spinner: &mut Spinner, done_str: &str) -> Result<(), Box<dyn Error>> {
-    let json_str = done_str.replace("done ", "");
-    let json_value: Value = from_str(json_str.as_str())?;
-    let assets = json_value["assets"].as_array().unwrap();
-    
-    spinner.stop();
-    clear_console()?;
+fn print_comparison(before: &Baseline, after: &Baseline, format: CompareFormat) {
+    let (assets, total) = baseline::compare(before, after);
+    let changed: Vec<&SizeDiff> = assets.iter().filter(|diff| diff.change() != Change::Unchanged).collect();
 
-    println!("✅ Application build completed!");
-    println!("");
-    println!("{:60} {:10}", "File", "Size");
-    println!("");
+    match format {
+        CompareFormat::Table => {
+            output::print("");
+            output::print(&format!("{:60} {:12} {:12} {:12} {}", "Asset", "Before", "After", "Delta", "Change"));
+            output::print("");
+            for diff in changed.iter().copied().chain([&total]) {
+                output::print(&format!(
+                    "{:60} {:12} {:12} {:12} {}",
+                    diff.name,
+                    size_or_dash(diff.before),
+                    size_or_dash(diff.after),
+                    format_delta(diff.delta()),
+                    change(diff)
+                ));
+            }
+        }
+        CompareFormat::Markdown => {
+            println!("| Asset | Before | After | Delta | Change |");
+            println!("|-------|-------:|------:|------:|-------:|");
+            for diff in changed.iter().copied() {
+                println!(
+                    "| `{}` | {} | {} | {} | {} |",
+                    diff.name,
+                    size_or_dash(diff.before),
+                    size_or_dash(diff.after),
+                    format_delta(diff.delta()),
+                    change(diff)
+                );
+            }
+            println!(
+                "| **{}** | **{}** | **{}** | **{}** | **{}** |",
+                total.name,
+                size_or_dash(total.before),
+                size_or_dash(total.after),
+                format_delta(total.delta()),
+                change(&total)
+            );
+        }
+    }
+}
+
+fn size_or_dash(size: Option<u64>) -> String {
+    size.map_or("-".into(), convert_bytes)
+}
 
-    for (_index, item) in assets.iter().enumerate() {
-        println!("{:60} {:10}", item["name"].as_str().unwrap(), convert_bytes(item["size"].as_u64().unwrap()));
+fn change(diff: &SizeDiff) -> String {
+    match diff.change() {
+        Change::Added => "added".into(),
+        Change::Removed => "removed".into(),
+        _ => format!("{:+.1}%", diff.percent().unwrap_or_default()),
+    }
+}
+
+fn print_budgets(checks: &[BudgetCheck]) {
+    if checks.is_empty() {
+        return;
+    }
+    output::print("");
+    output::print(&format!("{:60} {:14} {:14} {}", "Budget", "Actual", "Limit", "Status"));
+    output::print("");
+    for check in checks {
+        let status = match check.status {
+            BudgetStatus::Pass => "✅ pass",
+            BudgetStatus::Fail => "❌ fail",
+            BudgetStatus::Skipped => "➖ skipped",
+        };
+        let line = format!("{:60} {:14} {:14} {}", check.name, check.actual, check.limit, status);
+        match check.status {
+            BudgetStatus::Fail => output::text(&line),
+            _ => output::print(&line),
+        }
+    }
+}
+
+fn done(compilation: &Compilation, strict: bool) -> Result<(), Box<dyn Error>> {
+    if !compilation.errors.is_empty() {
+        print_problems(
+            &format!("❌ Failed to compile with {} error(s):", compilation.errors.len()),
+            &compilation.errors,
+        );
+        return Err(failure(&compilation.errors).into());
+    }
+    if strict && !compilation.warnings.is_empty() {
+        print_problems(
+            &format!("❌ Failed to compile with {} warning(s) in strict mode:", compilation.warnings.len()),
+            &compilation.warnings,
+        );
+        return Err(failure(&compilation.warnings).into());
+    }
+
+    let mut assets = compilation.stats["assets"].as_array().cloned().unwrap_or_default();
+    assets.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    output::info(&format!("✅ Application build completed in {:.2} s!", compilation.duration as f64 / 1000.0));
+    if let Some(cache) = compilation.cache {
+        let total = (cache.hits + cache.misses).max(1);
+        output::info(&format!(
+            "Cache: {} hit(s), {} miss(es), {:.0}% of modules restored",
+            cache.hits,
+            cache.misses,
+            cache.hits as f64 * 100.0 / total as f64
+        ));
+    }
+    output::print("");
+    output::print(&format!("{:60} {}", "File", "Size"));
+    output::print("");
+
+    for item in assets.iter() {
+        output::print(&format!(
+            "{:60} {}",
+            item["name"].as_str().unwrap_or_default(),
+            convert_bytes(item["size"].as_u64().unwrap_or_default())
+        ));
+    }
+
+    if !compilation.warnings.is_empty() {
+        output::print("");
+        print_problems(&format!("⚠️  Compiled with {} warning(s):", compilation.warnings.len()), &compilation.warnings);
     }
 
     Ok(())
 }
+
+fn failure(problems: &[Problem]) -> ExitError {
+    let mut modules: Vec<&str> = Vec::new();
+    for file in problems.iter().filter_map(|problem| problem.file.as_deref()) {
+        if !modules.contains(&file) {
+            modules.push(file);
+        }
+    }
+    if !modules.is_empty() {
+        output::text("");
+        output::warn("Failing modules:");
+        for module in &modules {
+            output::text(&format!("  {}", module));
+        }
+    }
+    let is_missing_module = problems
+        .iter()
+        .any(|problem| problem.message.contains("Module not found") || problem.message.contains("Can't resolve"));
+    match is_missing_module {
+        true => ExitError::new(EXIT_MISSING_MODULES, "The build failed because of missing modules"),
+        false => ExitError::new(EXIT_COMPILATION_ERRORS, "The build failed"),
+    }
+}
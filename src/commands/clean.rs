@@ -0,0 +1,49 @@
+use crate::libs::cache::CACHE_DIR;
+use crate::libs::helpers::remove_output_dir;
+use crate::libs::output;
+use crate::libs::project_config::ProjectConfig;
+use clap::Args;
+use std::error::Error;
+use std::fs;
+
+const LYRN_DIR: &str = ".lyrn";
+
+#[derive(Debug, Args)]
+pub struct CleanArgs {
+    #[arg(long, help = "Remove the build cache")]
+    cache: bool,
+    #[arg(long, help = "Remove the output folder")]
+    dist: bool,
+    #[arg(long, help = "Remove the output folder and everything lyrn keeps in the .lyrn folder")]
+    all: bool,
+}
+
+pub fn cmd(clean_args: CleanArgs) -> Result<(), Box<dyn Error>> {
+    let project_config = ProjectConfig::get(&None);
+    let is_default = !clean_args.cache && !clean_args.dist && !clean_args.all;
+    let mut dirs: Vec<&str> = Vec::new();
+
+    if clean_args.all {
+        dirs.push(LYRN_DIR);
+    } else if clean_args.cache || is_default {
+        dirs.push(CACHE_DIR);
+    }
+
+    for dir in dirs {
+        match fs::metadata(dir) {
+            Ok(_) => {
+                fs::remove_dir_all(dir)?;
+                output::info(&format!("✅ {} has been removed", dir));
+            }
+            Err(_) => output::debug(&format!("{} does not exist", dir)),
+        }
+    }
+    if clean_args.dist || clean_args.all || is_default {
+        let dir = &project_config.paths.output;
+        match remove_output_dir(dir)? {
+            true => output::info(&format!("✅ {} has been removed", dir)),
+            false => output::debug(&format!("{} does not exist", dir)),
+        }
+    }
+    Ok(())
+}
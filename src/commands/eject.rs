@@ -0,0 +1,350 @@
+use crate::libs::browserslist;
+use crate::libs::generated;
+use crate::libs::output;
+use crate::libs::project_config::{Minifier, ProjectConfig, Transpiler, PROJECT_CONFIG};
+use crate::tools::js::ModuleFormat;
+use crate::tools::webpack::{self, WebpackConfig};
+use clap::Args;
+use inquire::Confirm;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+
+const PACKAGE_JSON: &str = "package.json";
+const NODE_MODULES: &str = "node_modules";
+const SCRIPT: &str = "scripts/webpack.js";
+/// The helpers the lyrn bridge merges a custom config with, `scripts/webpack.js` requires them.
+const MERGE_SCRIPT: (&str, &str) = ("scripts/merge.js", include_str!("../../npm/tools/merge.js"));
+const CONFIG_DEV: &str = "webpack.config.dev.js";
+const CONFIG_PROD: &str = "webpack.config.prod.js";
+const BASE_DEV: &str = "webpack.base.dev.js";
+const BASE_PROD: &str = "webpack.base.prod.js";
+/// Modules of the lyrn npm package the generated configs import: the ejected project gets a copy.
+const HELPERS: [(&str, &str, &str); 1] = [(webpack::TARGETS_MODULE, "scripts/targets.js", include_str!("../../npm/tools/targets.js"))];
+
+/// A generated configuration written by eject, with the custom configuration lyrn merged into it.
+struct Ejected {
+    config: String,
+    custom: Option<String>,
+    names: Value,
+}
+
+#[derive(Debug, Args)]
+pub struct EjectArgs {
+    #[arg(short, long, help = "Do not ask for confirmation")]
+    yes: bool,
+}
+
+pub fn cmd(eject_args: EjectArgs) -> Result<(), Box<dyn Error>> {
+    if fs::metadata(PACKAGE_JSON).is_err() {
+        return Err(format!("File {} does not exist! Run the `eject` command only in the project folder.", PACKAGE_JSON).into());
+    }
+    if !eject_args.yes && !confirm()? {
+        return Ok(());
+    }
+
+    let project_config = ProjectConfig::get(&None);
+    browserslist::sync(&project_config)?;
+    let mut dev = webpack::get_config_dev(&project_config);
+    let mut prod = webpack::get_config_prod(&project_config);
+    let mut changes: Vec<String> = Vec::new();
+
+    fs::create_dir_all("scripts")?;
+    copy_helpers([&mut dev, &mut prod], &mut changes)?;
+
+    let dev_ejected = write_config(&dev, &project_config.dev.config, [CONFIG_DEV, BASE_DEV], &mut changes)?;
+    let prod_ejected = write_config(&prod, &project_config.prod.config, [CONFIG_PROD, BASE_PROD], &mut changes)?;
+
+    fs::write(SCRIPT, script(&dev_ejected, &prod_ejected, &project_config)?)?;
+    changes.push(format!("Wrote {}", SCRIPT));
+
+    let mut packages: BTreeSet<String> = dev.packages().union(&prod.packages()).cloned().collect();
+    packages.insert("webpack-dev-server".into());
+    packages.extend(minifier_packages(&project_config.optimization.minifier));
+    packages.extend(transpiler_packages(&project_config.transpiler));
+    let customs: Vec<&String> = [&dev_ejected, &prod_ejected].iter().filter_map(|ejected| ejected.custom.as_ref()).collect();
+    if !customs.is_empty() {
+        let (file, content) = MERGE_SCRIPT;
+        fs::write(file, content)?;
+        changes.push(format!("Wrote {}", file));
+        packages.insert("webpack-merge".into());
+    }
+    if customs.iter().any(|file| file.ends_with(".ts")) {
+        packages.insert("ts-node".into());
+    }
+    let versions = project_config.app.framework.dev_dependencies(project_config.transpiler);
+    let mut package: Value = serde_json::from_str(&fs::read_to_string(PACKAGE_JSON)?)?;
+    changes.extend(update_package(&mut package, &packages, &versions, Path::new(NODE_MODULES)));
+    fs::write(PACKAGE_JSON, serde_json::to_string_pretty(&package)? + "\n")?;
+
+    if fs::metadata(PROJECT_CONFIG).is_ok() {
+        fs::remove_file(PROJECT_CONFIG)?;
+        changes.push(format!("Removed {}", PROJECT_CONFIG));
+    }
+
+    output::info("✅ The project has been ejected from lyrn:");
+    for change in changes {
+        output::print(&format!("  • {}", change));
+    }
+    output::info("Run `npm install` to install the new dependencies");
+    Ok(())
+}
+
+fn confirm() -> Result<bool, Box<dyn Error>> {
+    if !output::is_interactive() || !std::io::stdin().is_terminal() {
+        return Err("Eject can not be undone. Run `lyrn eject --yes` to confirm it".into());
+    }
+    Ok(
+        Confirm::new("Eject can not be undone: lyrn will no longer manage the webpack configuration. Continue?")
+            .with_default(false)
+            .prompt()?,
+    )
+}
+
+/// Writes the generated config. A custom config written or edited by hand is kept, the generated config then goes to
+/// `webpack.base.*.js` and `scripts/webpack.js` merges the custom config into it the way lyrn does.
+fn write_config(config: &WebpackConfig, custom: &str, [file, base]: [&str; 2], changes: &mut Vec<String>) -> Result<Ejected, Box<dyn Error>> {
+    let custom = match fs::read_to_string(custom) {
+        Ok(content) if generated::is_modified(&content) => Some(custom.to_string()),
+        Ok(_) => {
+            if custom != file {
+                fs::remove_file(custom)?;
+                changes.push(format!("Removed {}", custom));
+            }
+            None
+        }
+        Err(_) => None,
+    };
+    let file = if custom.is_some() { base } else { file };
+    fs::write(file, config.to_module().print(ModuleFormat::Cjs))?;
+    changes.push(format!("Wrote {}", file));
+    if let Some(custom) = &custom {
+        changes.push(format!("Kept {}, {} merges it into {}", custom, SCRIPT, file));
+    }
+    Ok(Ejected {
+        config: file.into(),
+        custom,
+        names: config.names(),
+    })
+}
+
+fn copy_helpers(mut configs: [&mut WebpackConfig; 2], changes: &mut Vec<String>) -> Result<(), Box<dyn Error>> {
+    for (module, file, content) in HELPERS {
+        let imports = configs
+            .iter_mut()
+            .flat_map(|config| config.imports.iter_mut())
+            .filter(|import| import.module == module);
+        let mut imported = false;
+        for import in imports {
+            import.module = format!("./{}", file.trim_end_matches(".js"));
+            imported = true;
+        }
+        if imported {
+            fs::write(file, content)?;
+            changes.push(format!("Wrote {}", file));
+        }
+    }
+    Ok(())
+}
+
+fn minifier_packages(minifier: &Minifier) -> Option<String> {
+    match minifier {
+        Minifier::Terser => None,
+        Minifier::Esbuild => Some("esbuild".into()),
+        Minifier::Swc => Some("@swc/core".into()),
+    }
+}
+
+fn transpiler_packages(transpiler: &Transpiler) -> Option<String> {
+    match transpiler {
+        Transpiler::Ts => None,
+        Transpiler::Babel => Some("@babel/core".into()),
+        Transpiler::Swc => Some("@swc/core".into()),
+    }
+}
+
+/// The version of a package in the lyrn templates, otherwise the installed one.
+fn package_version(name: &str, versions: &Value, node_modules: &Path) -> Option<String> {
+    if let Some(version) = versions[name].as_str() {
+        return Some(version.into());
+    }
+    let content = fs::read_to_string(node_modules.join(name).join(PACKAGE_JSON)).ok()?;
+    let package: Value = serde_json::from_str(&content).ok()?;
+    package["version"].as_str().map(|version| format!("^{}", version))
+}
+
+fn update_package(package: &mut Value, packages: &BTreeSet<String>, versions: &Value, node_modules: &Path) -> Vec<String> {
+    let mut changes: Vec<String> = Vec::new();
+    if !package["scripts"].is_object() {
+        package["scripts"] = json!({});
+    }
+    package["scripts"]["start"] = json!(format!("node {} start", SCRIPT));
+    package["scripts"]["build"] = json!(format!("node {} build", SCRIPT));
+    changes.push(format!("Updated package.json scripts `start` and `build` to run {}", SCRIPT));
+
+    for key in ["dependencies", "devDependencies"] {
+        if let Some(dependencies) = package[key].as_object_mut() {
+            if dependencies.remove("lyrn").is_some() {
+                changes.push(format!("Removed lyrn from {}", key));
+            }
+        }
+    }
+
+    let installed = |name: &str| ["dependencies", "devDependencies"].iter().any(|key| package[key].get(name).is_some());
+    let missing: Vec<&String> = packages.iter().filter(|name| !installed(name)).collect();
+    if missing.is_empty() {
+        return changes;
+    }
+    if !package["devDependencies"].is_object() {
+        package["devDependencies"] = Value::Object(Map::new());
+    }
+    for name in missing {
+        match package_version(name, versions, node_modules) {
+            Some(version) => {
+                package["devDependencies"][name.as_str()] = json!(version);
+                changes.push(format!("Added {}@{} to devDependencies", name, version));
+            }
+            None => output::warn(&format!("⚠️  Could not find the version of {}. Install it with `npm i -D {}`", name, name)),
+        }
+    }
+    changes
+}
+
+fn script(dev: &Ejected, prod: &Ejected, project_config: &ProjectConfig) -> Result<String, Box<dyn Error>> {
+    let entry = |ejected: &Ejected, mode: &str| json!({ "mode": mode, "config": ejected.config, "custom": ejected.custom, "names": ejected.names });
+    let config = json!({ "start": entry(dev, "development"), "build": entry(prod, "production") });
+    Ok(SCRIPT_TEMPLATE
+        .replace("{{config}}", &serde_json::to_string_pretty(&config)?)
+        .replace("{{merge}}", &serde_json::to_string(&project_config.merge)?))
+}
+
+const SCRIPT_TEMPLATE: &str = r###"'use strict';
+
+const webpack = require('webpack');
+const WebpackDevServer = require('webpack-dev-server');
+const { resolve, extname } = require('path');
+const { pathToFileURL } = require('url');
+
+const CONFIG = {{config}};
+
+// How arrays and objects of a custom configuration are merged into the generated one.
+const MERGE = {{merge}};
+
+async function loadConfig(file) {
+  switch (extname(file)) {
+    case '.ts': {
+      require('ts-node').register({
+        transpileOnly: true,
+        compilerOptions: { module: 'commonjs', importHelpers: false },
+      });
+      const exports = require(file);
+      return exports.default || exports;
+    }
+    case '.mjs':
+      return (await import(pathToFileURL(file).href)).default;
+    default:
+      return require(file);
+  }
+}
+
+async function getConfig({ mode, config: file, custom, names }) {
+  const config = await loadConfig(resolve(file));
+  if (!custom) {
+    return config;
+  }
+  const { applyNames, mergeConfig } = require('./merge');
+  return mergeConfig(applyNames(config, names), await loadConfig(resolve(custom)), { mode, strategies: MERGE });
+}
+
+async function main(command) {
+  if (!(command in CONFIG)) {
+    console.error('Usage: node scripts/webpack.js start|build');
+    process.exit(1);
+  }
+  const config = await getConfig(CONFIG[command]);
+  const compiler = webpack(config);
+
+  if (command === 'start') {
+    const server = new WebpackDevServer(config.devServer, compiler);
+    await server.start();
+    return;
+  }
+  compiler.run((err, stats) => {
+    if (err) {
+      console.error(err);
+      process.exit(1);
+    }
+    console.log(stats.toString({ colors: true, modules: false, children: false }));
+    compiler.close(() => process.exit(stats.hasErrors() ? 1 : 0));
+  });
+}
+
+main(process.argv[2]).catch((err) => {
+  console.error(err);
+  process.exit(1);
+});
+"###;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::project_config::MergeStrategy;
+
+    #[test]
+    fn test_update_package() {
+        let mut package = json!({
+            "name": "app",
+            "scripts": { "start": "lyrn start", "build": "lyrn build", "serve": "serve dist" },
+            "dependencies": { "lyrn": "^1.2.2", "vue": "^3.3.4" },
+            "devDependencies": { "webpack": "^5.89.0" }
+        });
+        let packages = BTreeSet::from(["webpack".into(), "vue-loader".into(), "@swc/core".into(), "esbuild".into()]);
+        let versions = json!({ "vue-loader": "^17.1.1" });
+        let node_modules = tempfile::tempdir().unwrap();
+        fs::create_dir_all(node_modules.path().join("@swc/core")).unwrap();
+        fs::write(
+            node_modules.path().join("@swc/core/package.json"),
+            r#"{ "name": "@swc/core", "version": "1.3.96" }"#,
+        )
+        .unwrap();
+
+        let changes = update_package(&mut package, &packages, &versions, node_modules.path());
+
+        assert_eq!(
+            package,
+            json!({
+                "name": "app",
+                "scripts": { "start": "node scripts/webpack.js start", "build": "node scripts/webpack.js build", "serve": "serve dist" },
+                "dependencies": { "vue": "^3.3.4" },
+                "devDependencies": { "webpack": "^5.89.0", "@swc/core": "^1.3.96", "vue-loader": "^17.1.1" }
+            })
+        );
+        assert_eq!(changes.len(), 4);
+    }
+
+    #[test]
+    fn test_script() {
+        let mut project_config = ProjectConfig::default();
+        project_config.merge.insert("plugins".into(), MergeStrategy::Merge);
+        let ejected = |config: &str, custom: Option<&str>| Ejected {
+            config: config.into(),
+            custom: custom.map(String::from),
+            names: json!({ "rules": ["typescript"], "plugins": [] }),
+        };
+
+        let script = script(&ejected(CONFIG_DEV, None), &ejected(BASE_PROD, Some("webpack.custom.js")), &project_config).unwrap();
+
+        assert!(script.contains(
+            r#""config": "webpack.config.dev.js",
+    "custom": null,"#
+        ));
+        assert!(script.contains(
+            r#""config": "webpack.base.prod.js",
+    "custom": "webpack.custom.js","#
+        ));
+        assert!(script.contains(r#"const MERGE = {"plugins":"merge"};"#));
+    }
+}
@@ -1,6 +1,10 @@
-use crate::{tools::webpack, libs::project_config::EnvType};
+use crate::{
+    libs::{output, project_config::EnvType},
+    tools::{js::ModuleFormat, webpack},
+};
 use clap::{Args, Subcommand};
-use std::error::Error;
+use serde_json::Value;
+use std::{error::Error, fs};
 
 #[derive(Debug, Args)]
 #[command(args_conflicts_with_subcommands = true)]
@@ -19,6 +23,17 @@ enum ExportCommands {
 pub struct ExportConfigArgs {
     #[arg(short, long)]
     show: bool,
+    #[arg(long, value_enum, default_value_t = ModuleFormat::Cjs, help = "Module format of the exported files")]
+    format: ModuleFormat,
+    #[arg(short, long, help = "Overwrite exported files even if they have been modified")]
+    force: bool,
+    #[arg(
+        short,
+        long,
+        conflicts_with = "show",
+        help = "Show the difference between the exported files and the current configuration"
+    )]
+    diff: bool,
 }
 
 pub fn cmd(export_args: ExportArgs) -> Result<(), Box<dyn Error>> {
@@ -30,11 +45,35 @@ pub fn cmd(export_args: ExportArgs) -> Result<(), Box<dyn Error>> {
 
 fn export_config(args: ExportConfigArgs) -> Result<(), Box<dyn Error>> {
     if args.show {
-        let _ = webpack::show_config(EnvType::Dev);
-        let _ = webpack::show_config(EnvType::Prod);
+        let _ = webpack::show_config(EnvType::Dev, args.format);
+        let _ = webpack::show_config(EnvType::Prod, args.format);
         return Ok(());
     }
-    let _ = webpack::export_config(EnvType::Dev);
-    let _ = webpack::export_config(EnvType::Prod);
+    let envs = [EnvType::Dev, EnvType::Prod];
+    if args.diff {
+        for env_type in envs {
+            webpack::diff_config(env_type, args.format)?;
+        }
+        return Ok(());
+    }
+    if !args.force {
+        for env_type in envs {
+            webpack::check_export(env_type, args.format)?;
+        }
+    }
+    if args.format == ModuleFormat::Ts && !has_dependency("ts-node") {
+        output::warn("⚠️  TypeScript configuration files are loaded with ts-node. Install it with `npm i -D ts-node`");
+    }
+    for env_type in envs {
+        webpack::export_config(env_type, args.format)?;
+    }
     Ok(())
 }
+
+fn has_dependency(name: &str) -> bool {
+    let package: Value = fs::read_to_string("package.json")
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+    ["dependencies", "devDependencies"].iter().any(|key| package[key].get(name).is_some())
+}
@@ -0,0 +1,79 @@
+use crate::libs::output;
+use crate::libs::project_config::{EnvType, ProjectConfig};
+use crate::tools::bridge::{self, Bridge, Event};
+use crate::tools::js::Expr;
+use crate::tools::webpack;
+use clap::Args;
+use std::error::Error;
+use std::fs;
+
+#[derive(Debug, Args)]
+pub struct InspectArgs {
+    script: Option<String>,
+    #[arg(short, long, value_enum, default_value_t = EnvType::Dev, help = "Configuration to inspect")]
+    mode: EnvType,
+    #[arg(long, value_name = "PATH", help = "Print only a part of the configuration, e.g. module.rules or plugins.0")]
+    path: Option<String>,
+    #[arg(short, long, help = "Dev server port, as passed to `lyrn start`")]
+    port: Option<i32>,
+}
+
+pub fn cmd(inspect_args: InspectArgs) -> Result<(), Box<dyn Error>> {
+    let script = inspect_args.script.unwrap_or(bridge::SCRIPT.into());
+    if fs::metadata(&script).is_err() {
+        return Err(format!("File {} does not exist! Run the `inspect` command only in the project folder.", script).into());
+    }
+    let mut project_config = ProjectConfig::get(&None);
+    if let Some(port) = inspect_args.port {
+        project_config.dev.port = port;
+    }
+    let (webpack_config, mode) = match inspect_args.mode {
+        EnvType::Dev => (webpack::get_config_dev(&project_config), "--dev"),
+        EnvType::Prod => (webpack::get_config_prod(&project_config), "--prod"),
+    };
+    let mut bridge = Bridge::spawn(&script, &["inspect", mode], &webpack_config)?;
+    let mut config: Option<Expr> = None;
+    for event in bridge.events()? {
+        match event {
+            Event::Config { config: value } => config = Some(Expr::from_bridge(value)),
+            Event::Fatal { message, stack } => {
+                output::debug(&stack);
+                bridge.wait()?;
+                return Err(message.into());
+            }
+            _ => (),
+        }
+    }
+    bridge.wait()?;
+    let config = config.ok_or("The webpack bridge did not return the configuration")?;
+    let config = match &inspect_args.path {
+        Some(path) => select(&config, path).ok_or(format!("The configuration has no value at `{}`", path))?,
+        None => &config,
+    };
+    output::text(&config.to_js());
+    Ok(())
+}
+
+/// Follows a dotted path through objects and arrays, e.g. `module.rules.0.use`.
+fn select<'a>(config: &'a Expr, path: &str) -> Option<&'a Expr> {
+    path.split('.').filter(|key| !key.is_empty()).try_fold(config, |expr, key| match expr {
+        Expr::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => expr.get(key),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_select() {
+        let config = Expr::from(json!({ "module": { "rules": [{ "test": "ts" }, { "test": "css" }] } }));
+
+        assert_eq!(select(&config, "module.rules.1.test"), Some(&Expr::from("css")));
+        assert_eq!(select(&config, "module.rules.2"), None);
+        assert_eq!(select(&config, "module.loaders"), None);
+        assert_eq!(select(&config, ""), Some(&config));
+    }
+}
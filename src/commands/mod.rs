@@ -1,4 +1,7 @@
-pub mod create;
-pub mod start;
 pub mod build;
+pub mod clean;
+pub mod create;
+pub mod eject;
 pub mod export;
+pub mod inspect;
+pub mod start;
@@ -1,13 +1,18 @@
-use crate::libs::helpers::{clear_console, spinner_start};
+use crate::libs::browserslist;
+use crate::libs::certificates;
+use crate::libs::declarations;
+use crate::libs::helpers::print_problems;
+use crate::libs::output::{self, Progress};
 use crate::libs::project_config::ProjectConfig;
+use crate::tools::bridge::{self, Bridge, Compilation, Event};
 use crate::tools::webpack;
 use clap::Args;
+use inquire::Confirm;
 use local_ip_address::local_ip;
-use spinners::Spinner;
 use std::error::Error;
 use std::fs;
-use std::io::{BufRead, Write};
-use std::process::{Command, Stdio};
+use std::io::IsTerminal;
+use std::net::TcpListener;
 use std::thread;
 
 #[derive(Debug, Args, Clone)]
@@ -16,53 +21,148 @@ pub struct StartArgs {
     script: Option<String>,
     #[arg(short, long)]
     pub port: Option<i32>,
+    #[arg(long, help = "Host to bind the dev server to, use 0.0.0.0 to listen on all interfaces")]
+    pub host: Option<String>,
+    #[arg(long, help = "Serve the application over HTTPS")]
+    pub https: bool,
 }
 
 pub fn cmd(start_args: StartArgs) -> Result<(), Box<dyn Error>> {
-    let script = start_args.clone().script.or(Some("node_modules/lyrn/tools/webpack.js".into())).unwrap();
-    if let Err(_) = fs::metadata(&script) {
+    let script = start_args.clone().script.unwrap_or(bridge::SCRIPT.into());
+    if fs::metadata(&script).is_err() {
         return Err(format!("File {} does not exist! Run the `start` command only in the project folder.", script).into());
     }
-    let mut child = Command::new("node")
-        .arg(&script)
-        .arg("start")
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("Failed to spawn child process");
-
-    let mut child_stdin: std::process::ChildStdin = child.stdin.take().expect("Failed to open stdin for child process");
-    let mut spinner = spinner_start("Loading...").unwrap();
-    let project_config = ProjectConfig::get(&Some(start_args));
-    let webpack_config = webpack::get_config_dev(true, &project_config);
-    let json_string = serde_json::to_string(&webpack_config).unwrap();
-    child_stdin.write_all(&json_string.as_bytes()).expect("Failed to write to child process stdin");
-    drop(child_stdin);
-    let stdout = child.stdout.take().expect("Failed to open stdout for child process");
+    let mut project_config = ProjectConfig::get(&Some(start_args));
+    project_config.dev.port = free_port(&project_config.dev.host, project_config.dev.port)?;
+    if project_config.dev.protocol == "https" && project_config.dev.https.cert.is_empty() {
+        let certificates = certificates::ensure(&https_hosts(&project_config))?;
+        project_config.dev.https.key = certificates.key.to_string_lossy().into_owned();
+        project_config.dev.https.cert = certificates.cert.to_string_lossy().into_owned();
+    }
+    browserslist::sync(&project_config)?;
+    declarations::sync(&project_config)?;
+    let webpack_config = webpack::get_config_dev(&project_config);
+    let mut bridge = Bridge::spawn(&script, &["start"], &webpack_config)?;
+    let events = bridge.events()?;
+    let mut progress = Some(Progress::start("Loading..."));
 
-    thread::spawn(move || {
-        let reader = std::io::BufReader::new(stdout);
-        for line in reader.lines() {
-            match line.unwrap().as_str() {
-                "compile" => spinner = spinner_start("Loading...").unwrap(),
-                "done" => done(&mut spinner, &project_config).unwrap(),
-                _ => (),
+    let handle = thread::spawn(move || -> Result<(), String> {
+        for event in events {
+            match event {
+                Event::Compile => {
+                    if progress.is_none() {
+                        progress = Some(Progress::start("Loading..."));
+                    }
+                }
+                Event::Progress { percent, message } => {
+                    if let Some(progress) = progress.as_mut() {
+                        progress.update(&format!("Loading... {}% {}", percent, message));
+                    }
+                }
+                Event::Done(compilation) => {
+                    if let Some(progress) = progress.take() {
+                        progress.stop();
+                    }
+                    done(&project_config, &compilation);
+                }
+                Event::Config { .. } => {}
+                Event::Fatal { message, stack } => {
+                    drop(progress.take());
+                    output::debug(&stack);
+                    return Err(message);
+                }
             }
         }
+        Ok(())
     });
-    let _ = child.wait();
+    bridge.wait()?;
+    handle.join().map_err(|_| "Failed to read the output of the child process")??;
     Ok(())
 }
 
-fn done(spinner: &mut Spinner, project_config: &ProjectConfig) -> Result<(), Box<dyn Error>> {
-    spinner.stop();
-    clear_console()?;
-    let local_ip = local_ip().unwrap();
-    println!("🚀 Your app running at:");
-    println!(
-        "🔗 Local:    {}://{}:{}",
-        &project_config.dev.protocol, &project_config.dev.host, &project_config.dev.port
-    );
-    println!("🔗 Network:  {}://{}:{}", &project_config.dev.protocol, &local_ip, &project_config.dev.port);
-    Ok(())
+fn free_port(host: &str, port: i32) -> Result<i32, Box<dyn Error>> {
+    if is_port_free(host, port) {
+        return Ok(port);
+    }
+    let Some(next_port) = (port + 1..=u16::MAX as i32).find(|next_port| is_port_free(host, *next_port)) else {
+        return Err(format!("Port {} is already in use and no free port was found after it", port).into());
+    };
+    if output::is_interactive() && std::io::stdin().is_terminal() {
+        let use_next_port = Confirm::new(&format!("Port {} is already in use. Use port {} instead?", port, next_port))
+            .with_default(true)
+            .prompt()?;
+        if !use_next_port {
+            return Err(format!("Port {} is already in use", port).into());
+        }
+    } else {
+        output::warn(&format!("⚠️  Port {} is already in use, using port {} instead", port, next_port));
+    }
+    Ok(next_port)
+}
+
+fn is_port_free(host: &str, port: i32) -> bool {
+    let Ok(port) = u16::try_from(port) else {
+        return false;
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    TcpListener::bind((host, port)).is_ok()
+}
+
+fn https_hosts(project_config: &ProjectConfig) -> Vec<String> {
+    let mut hosts: Vec<String> = vec!["localhost".into(), "127.0.0.1".into(), "::1".into(), project_config.dev.host.clone()];
+    if let Ok(local_ip) = local_ip() {
+        hosts.push(local_ip.to_string());
+    }
+    hosts.extend(project_config.dev.https.hosts.clone());
+    let mut unique_hosts: Vec<String> = Vec::new();
+    for host in hosts {
+        if !host.is_empty() && !unique_hosts.contains(&host) {
+            unique_hosts.push(host);
+        }
+    }
+    unique_hosts
+}
+
+fn done(project_config: &ProjectConfig, compilation: &Compilation) {
+    if !compilation.errors.is_empty() {
+        print_problems(
+            &format!("❌ Failed to compile with {} error(s):", compilation.errors.len()),
+            &compilation.errors,
+        );
+        return;
+    }
+    let dev = &project_config.dev;
+    output::info("🚀 Your app running at:");
+    output::print(&format!("🔗 Local:    {}://{}:{}", dev.protocol, dev.public_host(), dev.port));
+    if !dev.is_any_host() {
+        output::print("🔗 Network:  use --host 0.0.0.0 to expose");
+    } else if let Ok(local_ip) = local_ip() {
+        output::print(&format!("🔗 Network:  {}://{}:{}", dev.protocol, local_ip, dev.port));
+    }
+    if !dev.proxy.is_empty() {
+        output::print("🔀 Proxy:");
+        for (context, proxy) in &dev.proxy {
+            output::print(&format!("   {:20} → {}", context, proxy.target));
+        }
+    }
+    if !compilation.warnings.is_empty() {
+        output::print("");
+        print_problems(&format!("⚠️  Compiled with {} warning(s):", compilation.warnings.len()), &compilation.warnings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_port_free() {
+        let listener = TcpListener::bind(("localhost", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port() as i32;
+
+        assert!(!is_port_free("localhost", port));
+        drop(listener);
+        assert!(is_port_free("localhost", port));
+        assert!(!is_port_free("localhost", 70000));
+    }
 }
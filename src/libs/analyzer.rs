@@ -0,0 +1,410 @@
+use super::helpers::convert_bytes;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub const ANALYZER_FILE: &str = ".lyrn/analyze.html";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModuleInfo {
+    pub name: String,
+    pub size: u64,
+    pub chunks: Vec<String>,
+    pub issuers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Duplicate {
+    pub package: String,
+    pub copies: Vec<PackageCopy>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PackageCopy {
+    pub path: String,
+    pub size: u64,
+    pub issuers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Analysis {
+    pub modules: Vec<ModuleInfo>,
+    pub duplicates: Vec<Duplicate>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    size: u64,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn insert(&mut self, path: &[&str], size: u64) {
+        self.size += size;
+        if let Some((first, rest)) = path.split_first() {
+            self.children.entry(first.to_string()).or_default().insert(rest, size);
+        }
+    }
+
+    fn to_json(&self, name: &str) -> Value {
+        let mut children: Vec<(&String, &Node)> = self.children.iter().collect();
+        children.sort_by(|a, b| b.1.size.cmp(&a.1.size).then(a.0.cmp(b.0)));
+        json!({
+            "name": name,
+            "size": self.size,
+            "children": children.iter().map(|(name, node)| node.to_json(name)).collect::<Vec<Value>>(),
+        })
+    }
+}
+
+impl Analysis {
+    pub fn new(stats: &Value) -> Self {
+        let chunk_names = chunk_names(stats);
+        let mut modules: Vec<ModuleInfo> = Vec::new();
+        for module in stats["modules"].as_array().into_iter().flatten() {
+            collect_modules(module, &[], &chunk_names, &mut modules);
+        }
+        modules.sort_by(|a, b| b.size.cmp(&a.size).then(a.name.cmp(&b.name)));
+        let duplicates = duplicates(&modules);
+        Self { modules, duplicates }
+    }
+
+    pub fn tree(&self) -> Value {
+        let mut root = Node::default();
+        for module in &self.modules {
+            let segments: Vec<&str> = module.name.split('/').filter(|segment| !segment.is_empty() && *segment != ".").collect();
+            for chunk in &module.chunks {
+                root.insert(&[&[chunk.as_str()], &segments[..]].concat(), module.size);
+            }
+        }
+        root.to_json("bundle")
+    }
+
+    pub fn write_html(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let data = serde_json::to_string(&self.tree())?.replace("</", "<\\/");
+        fs::write(path, TEMPLATE.replace("__DATA__", &data))?;
+        Ok(())
+    }
+}
+
+fn chunk_names(stats: &Value) -> BTreeMap<String, String> {
+    stats["chunks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|chunk| {
+            let id = id_to_string(&chunk["id"]);
+            let names: Vec<&str> = chunk["names"].as_array().into_iter().flatten().filter_map(|name| name.as_str()).collect();
+            let label = match names.is_empty() {
+                true => format!("chunk {}", id),
+                false => names.join(", "),
+            };
+            (id, label)
+        })
+        .collect()
+}
+
+fn collect_modules(module: &Value, parent_chunks: &[String], chunk_names: &BTreeMap<String, String>, modules: &mut Vec<ModuleInfo>) {
+    if module["orphan"].as_bool().unwrap_or_default() && parent_chunks.is_empty() {
+        return;
+    }
+    let mut chunks: Vec<String> = module["chunks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|id| {
+            let id = id_to_string(id);
+            chunk_names.get(&id).cloned().unwrap_or(format!("chunk {}", id))
+        })
+        .collect();
+    if chunks.is_empty() {
+        chunks = parent_chunks.to_vec();
+    }
+
+    if let Some(nested) = module["modules"].as_array().filter(|nested| !nested.is_empty()) {
+        for nested_module in nested {
+            collect_modules(nested_module, &chunks, chunk_names, modules);
+        }
+        return;
+    }
+
+    let name = clean_name(module["name"].as_str().unwrap_or_default());
+    let mut issuers: Vec<String> = Vec::new();
+    for reason in module["reasons"].as_array().into_iter().flatten() {
+        if let Some(issuer) = reason["moduleName"].as_str().map(clean_name) {
+            if issuer != name && !issuers.contains(&issuer) {
+                issuers.push(issuer);
+            }
+        }
+    }
+    modules.push(ModuleInfo {
+        name,
+        size: module["size"].as_u64().unwrap_or_default(),
+        chunks,
+        issuers,
+    });
+}
+
+fn duplicates(modules: &[ModuleInfo]) -> Vec<Duplicate> {
+    let mut packages: BTreeMap<String, BTreeMap<String, PackageCopy>> = BTreeMap::new();
+    for module in modules {
+        let Some((package, path)) = package_of(&module.name) else {
+            continue;
+        };
+        let copy = packages.entry(package).or_default().entry(path.clone()).or_insert(PackageCopy {
+            path: path.clone(),
+            ..Default::default()
+        });
+        copy.size += module.size;
+        for issuer in module.issuers.iter().filter(|issuer| !issuer.starts_with(&path)) {
+            if !copy.issuers.contains(issuer) {
+                copy.issuers.push(issuer.clone());
+            }
+        }
+    }
+    packages
+        .into_iter()
+        .filter(|(_, copies)| copies.len() > 1)
+        .map(|(package, copies)| Duplicate {
+            package,
+            copies: copies.into_values().collect(),
+        })
+        .collect()
+}
+
+fn id_to_string(id: &Value) -> String {
+    match id {
+        Value::String(id) => id.clone(),
+        id => id.to_string(),
+    }
+}
+
+static CONCATENATED: OnceLock<Regex> = OnceLock::new();
+
+pub fn clean_name(name: &str) -> String {
+    let re = CONCATENATED.get_or_init(|| Regex::new(r" \+ \d+ modules?$").unwrap());
+    let name = name.rsplit('!').next().unwrap_or_default();
+    let name = re.replace(name, "");
+    name.strip_prefix("./").unwrap_or(&name).to_string()
+}
+
+pub fn package_of(name: &str) -> Option<(String, String)> {
+    const NODE_MODULES: &str = "node_modules/";
+    let start = name.rfind(NODE_MODULES)? + NODE_MODULES.len();
+    let segments: Vec<&str> = name[start..].split('/').collect();
+    let package = match segments.first()?.starts_with('@') {
+        true => segments.get(..2)?.join("/"),
+        false => segments.first()?.to_string(),
+    };
+    Some((package.clone(), format!("{}{}", &name[..start], package)))
+}
+
+pub fn summary(analysis: &Analysis, top: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![format!("{:80} {}", "Module", "Size"), String::new()];
+    for module in analysis.modules.iter().take(top) {
+        lines.push(format!("{:80} {}", module.name, convert_bytes(module.size)));
+    }
+    if !analysis.duplicates.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("Duplicated packages ({}):", analysis.duplicates.len()));
+        for duplicate in &analysis.duplicates {
+            lines.push(String::new());
+            lines.push(format!("  {}", duplicate.package));
+            for copy in &duplicate.copies {
+                lines.push(format!("    {} ({})", copy.path, convert_bytes(copy.size)));
+                if !copy.issuers.is_empty() {
+                    lines.push(format!("      required by {}", copy.issuers.join(", ")));
+                }
+            }
+        }
+    }
+    lines
+}
+
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8">
+  <meta name="viewport" content="width=device-width, initial-scale=1.0">
+  <title>lyrn bundle analyzer</title>
+  <style>
+    * { box-sizing: border-box; }
+    body { margin: 0; font: 13px/1.4 -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif; color: #1f2328; }
+    header { display: flex; align-items: center; gap: 12px; height: 40px; padding: 0 12px; background: #24292f; color: #fff; }
+    header a { color: #9ecbff; cursor: pointer; }
+    #map { position: absolute; top: 40px; right: 0; bottom: 0; left: 0; }
+    .node { position: absolute; overflow: hidden; padding: 2px 4px; border: 1px solid #fff; cursor: pointer; white-space: nowrap; text-overflow: ellipsis; }
+    .node:hover { filter: brightness(1.1); }
+  </style>
+</head>
+<body>
+  <header><strong>lyrn</strong><span id="path"></span></header>
+  <div id="map"></div>
+  <script>
+    const data = __DATA__;
+    const colors = ['#8dd3c7', '#ffffb3', '#bebada', '#fb8072', '#80b1d3', '#fdb462', '#b3de69', '#fccde5', '#d9d9d9', '#bc80bd'];
+
+    function format(bytes) {
+      return bytes >= 1048576 ? (bytes / 1048576).toFixed(2) + ' Mb' : (bytes / 1024).toFixed(2) + ' Kb';
+    }
+
+    function worst(row, side) {
+      const sum = row.reduce((total, item) => total + item.area, 0);
+      const max = Math.max(...row.map((item) => item.area));
+      const min = Math.min(...row.map((item) => item.area));
+      return Math.max((side * side * max) / (sum * sum), (sum * sum) / (side * side * min));
+    }
+
+    function layout(items, x, y, width, height) {
+      const rects = [];
+      let row = [];
+      items = items.slice();
+      while (items.length) {
+        const side = Math.min(width, height);
+        const item = items[0];
+        if (!row.length || worst(row, side) >= worst(row.concat(item), side)) {
+          row.push(items.shift());
+          continue;
+        }
+        ({ x, y, width, height } = place(row, x, y, width, height, rects));
+        row = [];
+      }
+      if (row.length) {
+        place(row, x, y, width, height, rects);
+      }
+      return rects;
+    }
+
+    function place(row, x, y, width, height, rects) {
+      const sum = row.reduce((total, item) => total + item.area, 0);
+      if (width >= height) {
+        const rowWidth = sum / height;
+        let offset = y;
+        for (const item of row) {
+          const itemHeight = item.area / rowWidth;
+          rects.push({ node: item.node, x, y: offset, width: rowWidth, height: itemHeight });
+          offset += itemHeight;
+        }
+        return { x: x + rowWidth, y, width: width - rowWidth, height };
+      }
+      const rowHeight = sum / width;
+      let offset = x;
+      for (const item of row) {
+        const itemWidth = item.area / rowHeight;
+        rects.push({ node: item.node, x: offset, y, width: itemWidth, height: rowHeight });
+        offset += itemWidth;
+      }
+      return { x, y: y + rowHeight, width, height: height - rowHeight };
+    }
+
+    function render(node, trail) {
+      const map = document.getElementById('map');
+      const path = document.getElementById('path');
+      map.innerHTML = '';
+      path.innerHTML = '';
+      trail.forEach((item, index) => {
+        const link = document.createElement(index === trail.length - 1 ? 'span' : 'a');
+        link.textContent = (index ? ' / ' : '') + item.name;
+        link.onclick = () => render(item, trail.slice(0, index + 1));
+        path.appendChild(link);
+      });
+      path.appendChild(document.createTextNode(' — ' + format(node.size)));
+
+      const width = map.clientWidth;
+      const height = map.clientHeight;
+      const children = node.children.filter((child) => child.size > 0);
+      const scale = (width * height) / Math.max(node.size, 1);
+      const items = children.map((child) => ({ node: child, area: child.size * scale }));
+      layout(items, 0, 0, width, height).forEach((rect, index) => {
+        const element = document.createElement('div');
+        element.className = 'node';
+        element.style.left = rect.x + 'px';
+        element.style.top = rect.y + 'px';
+        element.style.width = rect.width + 'px';
+        element.style.height = rect.height + 'px';
+        element.style.background = colors[index % colors.length];
+        element.textContent = rect.node.name + ' ' + format(rect.node.size);
+        element.title = rect.node.name + '\n' + format(rect.node.size);
+        if (rect.node.children.length) {
+          element.onclick = () => render(rect.node, trail.concat(rect.node));
+        }
+        map.appendChild(element);
+      });
+    }
+
+    render(data, [data]);
+    window.addEventListener('resize', () => render(data, [data]));
+  </script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_name() {
+        assert_eq!(clean_name("./node_modules/css-loader/dist/cjs.js!./src/styles/main.css"), "src/styles/main.css");
+        assert_eq!(clean_name("./src/main.ts + 12 modules"), "src/main.ts");
+    }
+
+    #[test]
+    fn test_package_of() {
+        assert_eq!(
+            package_of("node_modules/lodash/lodash.js"),
+            Some(("lodash".into(), "node_modules/lodash".into()))
+        );
+        assert_eq!(
+            package_of("node_modules/a/node_modules/@scope/b/index.js"),
+            Some(("@scope/b".into(), "node_modules/a/node_modules/@scope/b".into()))
+        );
+        assert_eq!(package_of("src/main.ts"), None);
+    }
+
+    #[test]
+    fn test_analysis() {
+        let stats = json!({
+            "chunks": [{ "id": 179, "names": ["main"] }],
+            "modules": [
+                {
+                    "name": "./src/main.ts + 2 modules",
+                    "size": 300,
+                    "chunks": [179],
+                    "modules": [
+                        { "name": "./src/main.ts", "size": 100 },
+                        { "name": "./src/app.ts", "size": 200 }
+                    ]
+                },
+                { "name": "./node_modules/lodash/lodash.js", "size": 5000, "chunks": [179], "reasons": [{ "moduleName": "./src/app.ts" }] },
+                {
+                    "name": "./node_modules/a/node_modules/lodash/lodash.js",
+                    "size": 4000,
+                    "chunks": [179],
+                    "reasons": [{ "moduleName": "./node_modules/a/index.js" }]
+                },
+                { "name": "./src/unused.ts", "size": 50, "orphan": true }
+            ]
+        });
+        let analysis = Analysis::new(&stats);
+
+        assert_eq!(analysis.modules.len(), 4);
+        assert_eq!(analysis.modules[0].name, "node_modules/lodash/lodash.js");
+        assert_eq!(analysis.modules[3].chunks, vec!["main".to_string()]);
+        assert_eq!(analysis.duplicates.len(), 1);
+        assert_eq!(analysis.duplicates[0].package, "lodash");
+        assert_eq!(analysis.duplicates[0].copies[1].issuers, vec!["src/app.ts".to_string()]);
+
+        let tree = analysis.tree();
+        assert_eq!(tree["size"], 9300);
+        assert_eq!(tree["children"][0]["name"], "main");
+        assert_eq!(tree["children"][0]["children"][0]["name"], "node_modules");
+    }
+}
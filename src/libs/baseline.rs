@@ -0,0 +1,174 @@
+use super::report::{Report, Sizes};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs::{self, read_to_string};
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub const BASELINE_FILE: &str = ".lyrn/baseline.json";
+pub const BASELINE_SCHEMA: u32 = 1;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub schema: u32,
+    pub git_commit: Option<String>,
+    pub assets: BTreeMap<String, Sizes>,
+    pub entrypoints: BTreeMap<String, Sizes>,
+    pub total: Sizes,
+}
+
+impl Baseline {
+    pub fn from_report(report: &Report) -> Self {
+        let mut assets: BTreeMap<String, Sizes> = BTreeMap::new();
+        for asset in &report.assets {
+            *assets.entry(strip_hash(&asset.name)).or_default() += asset.sizes;
+        }
+        Self {
+            schema: BASELINE_SCHEMA,
+            git_commit: report.git_commit.clone(),
+            assets,
+            entrypoints: report
+                .entrypoints
+                .iter()
+                .map(|entrypoint| (entrypoint.name.clone(), entrypoint.sizes))
+                .collect(),
+            total: report.total(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        self.save_to(Path::new(BASELINE_FILE))
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    pub fn load() -> Option<Self> {
+        Self::load_from(Path::new(BASELINE_FILE))
+    }
+
+    pub fn load_from(path: &Path) -> Option<Self> {
+        from_str(&read_to_string(path).ok()?).ok()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeDiff {
+    pub name: String,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+}
+
+impl SizeDiff {
+    pub fn change(&self) -> Change {
+        match (self.before, self.after) {
+            (None, _) => Change::Added,
+            (_, None) => Change::Removed,
+            (before, after) if before == after => Change::Unchanged,
+            _ => Change::Changed,
+        }
+    }
+
+    pub fn delta(&self) -> i64 {
+        self.after.unwrap_or_default() as i64 - self.before.unwrap_or_default() as i64
+    }
+
+    pub fn percent(&self) -> Option<f64> {
+        match (self.before, self.after) {
+            (Some(before), Some(_)) if before > 0 => Some(self.delta() as f64 * 100.0 / before as f64),
+            _ => None,
+        }
+    }
+}
+
+pub fn compare(before: &Baseline, after: &Baseline) -> (Vec<SizeDiff>, SizeDiff) {
+    let names: BTreeSet<&String> = before.assets.keys().chain(after.assets.keys()).collect();
+    let assets = names
+        .into_iter()
+        .map(|name| SizeDiff {
+            name: name.clone(),
+            before: before.assets.get(name).map(|sizes| sizes.size),
+            after: after.assets.get(name).map(|sizes| sizes.size),
+        })
+        .collect();
+    let total = SizeDiff {
+        name: "Total".into(),
+        before: Some(before.total.size),
+        after: Some(after.total.size),
+    };
+    (assets, total)
+}
+
+static HASH: OnceLock<Regex> = OnceLock::new();
+
+pub fn strip_hash(name: &str) -> String {
+    let re = HASH.get_or_init(|| Regex::new(r"\b[0-9a-f]{8,}\b").unwrap());
+    re.replace_all(name, "[hash]").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_hash() {
+        assert_eq!(strip_hash("js/main.3f2a1b9c8d7e6f5a.bundle.js"), "js/main.[hash].bundle.js");
+        assert_eq!(strip_hash("assets/0a1b2c3d4e5f6a7b.png"), "assets/[hash].png");
+        assert_eq!(strip_hash("index.html"), "index.html");
+    }
+
+    #[test]
+    fn test_compare() {
+        let sizes = |size: u64| Sizes { size, ..Default::default() };
+        let before = Baseline {
+            assets: BTreeMap::from([
+                ("js/main.[hash].bundle.js".to_string(), sizes(1000)),
+                ("js/vendor.[hash].bundle.js".to_string(), sizes(500)),
+                ("index.html".to_string(), sizes(100)),
+            ]),
+            total: sizes(1600),
+            ..Default::default()
+        };
+        let after = Baseline {
+            assets: BTreeMap::from([
+                ("js/main.[hash].bundle.js".to_string(), sizes(1100)),
+                ("js/admin.[hash].chunk.js".to_string(), sizes(300)),
+                ("index.html".to_string(), sizes(100)),
+            ]),
+            total: sizes(1500),
+            ..Default::default()
+        };
+        let (assets, total) = compare(&before, &after);
+        let changes: Vec<(&str, Change)> = assets.iter().map(|diff| (diff.name.as_str(), diff.change())).collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                ("index.html", Change::Unchanged),
+                ("js/admin.[hash].chunk.js", Change::Added),
+                ("js/main.[hash].bundle.js", Change::Changed),
+                ("js/vendor.[hash].bundle.js", Change::Removed),
+            ]
+        );
+        assert_eq!(assets[2].delta(), 100);
+        assert_eq!(assets[2].percent(), Some(10.0));
+        assert_eq!(assets[3].delta(), -500);
+        assert_eq!(total.delta(), -100);
+    }
+}
@@ -0,0 +1,59 @@
+use super::output;
+use super::project_config::ProjectConfig;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+const PACKAGE_JSON: &str = "package.json";
+const BROWSERSLISTRC: &str = ".browserslistrc";
+
+/// Writes `targets` from lyrn.json to the `browserslist` field of package.json,
+/// where PostCSS, Babel, swc and webpack read the target browsers from.
+pub fn sync(project_config: &ProjectConfig) -> Result<(), Box<dyn Error>> {
+    if project_config.targets.is_empty() {
+        return Ok(());
+    }
+    if Path::new(BROWSERSLISTRC).exists() {
+        output::warn(&format!(
+            "⚠️  {} overrides `targets` from lyrn.json, remove it to use `targets`",
+            BROWSERSLISTRC
+        ));
+        return Ok(());
+    }
+    let Ok(data) = fs::read_to_string(PACKAGE_JSON) else {
+        return Ok(());
+    };
+    let mut package: Value = serde_json::from_str(&data)?;
+    if update(&mut package, &project_config.targets) {
+        fs::write(PACKAGE_JSON, serde_json::to_string_pretty(&package)? + "\n")?;
+        output::info("Updated `browserslist` in package.json to match `targets` in lyrn.json");
+    }
+    Ok(())
+}
+
+fn update(package: &mut Value, targets: &[String]) -> bool {
+    let targets = json!(targets);
+    match package.as_object_mut() {
+        Some(package) if package.get("browserslist") != Some(&targets) => {
+            package.insert("browserslist".into(), targets);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let mut package = json!({ "name": "app", "browserslist": ["last 2 versions"] });
+        let targets = vec!["defaults".to_string(), "not op_mini all".to_string()];
+
+        assert!(update(&mut package, &targets));
+        assert_eq!(package["browserslist"], json!(["defaults", "not op_mini all"]));
+        assert!(!update(&mut package, &targets));
+    }
+}
@@ -0,0 +1,241 @@
+use super::baseline::{strip_hash, Baseline};
+use super::helpers::{convert_bytes, format_delta};
+use super::project_config::{Budget, BudgetsConfig, SizeCompression};
+use super::report::{Report, Sizes};
+use glob::Pattern;
+use std::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetStatus {
+    Pass,
+    Fail,
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetCheck {
+    pub name: String,
+    pub actual: String,
+    pub limit: String,
+    pub status: BudgetStatus,
+}
+
+pub fn check(budgets: &BudgetsConfig, report: &Report, baseline: Option<&Baseline>) -> Result<Vec<BudgetCheck>, Box<dyn Error>> {
+    let mut checks: Vec<BudgetCheck> = Vec::new();
+
+    for asset_budget in &budgets.assets {
+        let pattern = Pattern::new(&asset_budget.pattern).map_err(|err| format!("Invalid asset pattern `{}` in budgets: {}", asset_budget.pattern, err))?;
+        for asset in report.assets.iter().filter(|asset| pattern.matches(&asset.name)) {
+            let previous = baseline.and_then(|baseline| baseline.assets.get(&strip_hash(&asset.name)).copied());
+            check_budget(
+                &mut checks,
+                &format!("Asset {}", asset.name),
+                asset.sizes,
+                previous,
+                baseline.is_some(),
+                &asset_budget.budget,
+            )?;
+        }
+    }
+
+    for entrypoint_budget in &budgets.entrypoints {
+        let name = format!("Entrypoint {}", entrypoint_budget.name);
+        match report.entrypoints.iter().find(|entrypoint| entrypoint.name == entrypoint_budget.name) {
+            Some(entrypoint) => {
+                let previous = baseline.and_then(|baseline| baseline.entrypoints.get(&entrypoint.name).copied());
+                check_budget(&mut checks, &name, entrypoint.sizes, previous, baseline.is_some(), &entrypoint_budget.budget)?;
+            }
+            None => checks.push(BudgetCheck {
+                name,
+                actual: "not found".into(),
+                limit: "-".into(),
+                status: BudgetStatus::Fail,
+            }),
+        }
+    }
+
+    if let Some(budget) = &budgets.total {
+        let previous = baseline.map(|baseline| baseline.total);
+        check_budget(&mut checks, "Total", report.total(), previous, baseline.is_some(), budget)?;
+    }
+
+    Ok(checks)
+}
+
+fn check_budget(
+    checks: &mut Vec<BudgetCheck>,
+    name: &str,
+    current: Sizes,
+    previous: Option<Sizes>,
+    has_baseline: bool,
+    budget: &Budget,
+) -> Result<(), Box<dyn Error>> {
+    let name = match budget.compression {
+        SizeCompression::Raw => name.to_string(),
+        SizeCompression::Gzip => format!("{} (gzip)", name),
+        SizeCompression::Brotli => format!("{} (brotli)", name),
+    };
+    let actual = current.get(budget.compression);
+
+    if !budget.max_size.is_empty() {
+        let limit = parse_size(&budget.max_size)?;
+        checks.push(BudgetCheck {
+            name: name.clone(),
+            actual: convert_bytes(actual),
+            limit: convert_bytes(limit),
+            status: match actual <= limit {
+                true => BudgetStatus::Pass,
+                false => BudgetStatus::Fail,
+            },
+        });
+    }
+
+    if !budget.max_increase.is_empty() {
+        let name = format!("{} increase", name);
+        let limit = format!("+{}", budget.max_increase.trim());
+        match previous {
+            Some(previous) => {
+                let previous = previous.get(budget.compression);
+                let max_increase = parse_increase(&budget.max_increase, previous)?;
+                let increase = actual as i64 - previous as i64;
+                checks.push(BudgetCheck {
+                    name,
+                    actual: format_delta(increase),
+                    limit,
+                    status: match increase <= max_increase as i64 {
+                        true => BudgetStatus::Pass,
+                        false => BudgetStatus::Fail,
+                    },
+                });
+            }
+            None => checks.push(BudgetCheck {
+                name,
+                actual: match has_baseline {
+                    true => "new".into(),
+                    false => "no baseline".into(),
+                },
+                limit,
+                status: BudgetStatus::Skipped,
+            }),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn parse_size(size: &str) -> Result<u64, Box<dyn Error>> {
+    let size = size.trim().to_lowercase().replace(' ', "");
+    let split = size.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let multiplier: u64 = match unit {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1024,
+        "m" | "mb" | "mib" => 1024 * 1024,
+        "g" | "gb" | "gib" => 1024 * 1024 * 1024,
+        _ => return Err(format!("Invalid size `{}` in budgets", size).into()),
+    };
+    let number: f64 = number.parse().map_err(|_| format!("Invalid size `{}` in budgets", size))?;
+    Ok((number * multiplier as f64).round() as u64)
+}
+
+fn parse_increase(increase: &str, previous: u64) -> Result<u64, Box<dyn Error>> {
+    match increase.trim().strip_suffix('%') {
+        Some(percent) => {
+            let percent: f64 = percent.trim().parse().map_err(|_| format!("Invalid increase `{}` in budgets", increase))?;
+            Ok((previous as f64 * percent / 100.0).round() as u64)
+        }
+        None => parse_size(increase),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::project_config::{AssetBudget, EntrypointBudget};
+    use crate::libs::report::{AssetReport, EntrypointReport};
+    use std::collections::BTreeMap;
+
+    fn sizes(size: u64) -> Sizes {
+        Sizes {
+            size,
+            gzip: size / 4,
+            brotli: size / 5,
+        }
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512").unwrap(), 512);
+        assert_eq!(parse_size("250 KB").unwrap(), 256000);
+        assert_eq!(parse_size("1.5mb").unwrap(), 1572864);
+        assert!(parse_size("ten kb").is_err());
+        assert_eq!(parse_increase("10%", 2000).unwrap(), 200);
+    }
+
+    #[test]
+    fn test_check() {
+        let report = Report {
+            schema: 1,
+            lyrn_version: "1.3.0".into(),
+            mode: "production".into(),
+            git_commit: None,
+            hash: String::new(),
+            duration: 0,
+            assets: vec![
+                AssetReport {
+                    name: "js/main.0a1b2c3d4e.bundle.js".into(),
+                    sizes: sizes(400_000),
+                },
+                AssetReport {
+                    name: "index.html".into(),
+                    sizes: sizes(1_000),
+                },
+            ],
+            chunks: vec![],
+            entrypoints: vec![EntrypointReport {
+                name: "main".into(),
+                assets: vec!["js/main.0a1b2c3d4e.bundle.js".into()],
+                sizes: sizes(400_000),
+            }],
+            warnings: vec![],
+        };
+        let budgets = BudgetsConfig {
+            assets: vec![AssetBudget {
+                pattern: "js/*.js".into(),
+                budget: Budget {
+                    max_size: "120 KB".into(),
+                    compression: SizeCompression::Gzip,
+                    ..Default::default()
+                },
+            }],
+            entrypoints: vec![EntrypointBudget {
+                name: "main".into(),
+                budget: Budget {
+                    max_increase: "5%".into(),
+                    ..Default::default()
+                },
+            }],
+            total: Some(Budget {
+                max_size: "350 KB".into(),
+                ..Default::default()
+            }),
+        };
+        let baseline = Baseline {
+            entrypoints: BTreeMap::from([("main".to_string(), sizes(360_000))]),
+            ..Default::default()
+        };
+
+        let checks = check(&budgets, &report, Some(&baseline)).unwrap();
+        let statuses: Vec<(&str, BudgetStatus)> = checks.iter().map(|check| (check.name.as_str(), check.status)).collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                ("Asset js/main.0a1b2c3d4e.bundle.js (gzip)", BudgetStatus::Pass),
+                ("Entrypoint main increase", BudgetStatus::Fail),
+                ("Total", BudgetStatus::Fail),
+            ]
+        );
+        assert_eq!(check(&budgets, &report, None).unwrap()[1].status, BudgetStatus::Skipped);
+    }
+}
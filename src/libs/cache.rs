@@ -0,0 +1,17 @@
+use super::project_config::PROJECT_CONFIG;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+
+pub const CACHE_DIR: &str = ".lyrn/cache";
+const CACHE_KEY_FILES: [&str; 5] = [PROJECT_CONFIG, "tsconfig.json", "package-lock.json", "yarn.lock", "pnpm-lock.yaml"];
+
+pub fn version() -> String {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    for file in CACHE_KEY_FILES {
+        file.hash(&mut hasher);
+        fs::read(file).unwrap_or_default().hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
@@ -0,0 +1,184 @@
+use super::output;
+use chrono::{Datelike, Duration, Utc};
+use rcgen::{BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, KeyPair, KeyUsagePurpose};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const CA_NAME: &str = "lyrn local development CA";
+const CA_DIR: &str = ".lyrn/ca";
+const CERTS_DIR: &str = ".lyrn/certs";
+const CERT_VALIDITY_DAYS: i64 = 365;
+const CERT_RENEW_DAYS: u64 = 300;
+
+#[derive(Debug, Clone)]
+pub struct Certificates {
+    pub ca: PathBuf,
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+pub fn ensure(hosts: &[String]) -> Result<Certificates, Box<dyn Error>> {
+    ensure_in(&home_dir()?.join(CA_DIR), Path::new(CERTS_DIR), hosts)
+}
+
+fn ensure_in(ca_dir: &Path, certs_dir: &Path, hosts: &[String]) -> Result<Certificates, Box<dyn Error>> {
+    let certificates = Certificates {
+        ca: ca_dir.join("ca.pem"),
+        cert: certs_dir.join("cert.pem"),
+        key: certs_dir.join("key.pem"),
+    };
+    let hosts_file = certs_dir.join("hosts");
+    let ca_key_file = ca_dir.join("ca-key.pem");
+
+    let (ca, ca_key, ca_created) = match (fs::read_to_string(&ca_key_file), certificates.ca.is_file()) {
+        (Ok(ca_key_pem), true) => {
+            let ca_key = KeyPair::from_pem(&ca_key_pem)?;
+            (ca_params().self_signed(&ca_key)?, ca_key, false)
+        }
+        _ => {
+            let ca_key = KeyPair::generate()?;
+            let ca = ca_params().self_signed(&ca_key)?;
+            create_private_dir(ca_dir)?;
+            fs::write(&certificates.ca, ca.pem())?;
+            write_private(&ca_key_file, &ca_key.serialize_pem())?;
+            (ca, ca_key, true)
+        }
+    };
+
+    let hosts_str = hosts.join("\n");
+    let is_actual = !ca_created
+        && fs::read_to_string(&hosts_file).is_ok_and(|cached_hosts| cached_hosts == hosts_str)
+        && certificates.key.is_file()
+        && is_fresh(&certificates.cert);
+    if !is_actual {
+        let key = KeyPair::generate()?;
+        let cert = cert_params(hosts)?.signed_by(&key, &ca, &ca_key)?;
+        fs::create_dir_all(certs_dir)?;
+        fs::write(&certificates.cert, cert.pem())?;
+        write_private(&certificates.key, &key.serialize_pem())?;
+        fs::write(&hosts_file, hosts_str)?;
+    }
+
+    if ca_created {
+        output::info(&format!("🔐 A local certificate authority has been created: {}", certificates.ca.display()));
+        output::info("   Add it to the trusted certificates of your system or browser to avoid security warnings");
+    }
+
+    Ok(certificates)
+}
+
+fn ca_params() -> CertificateParams {
+    let mut params = CertificateParams::default();
+    params.distinguished_name.push(DnType::CommonName, CA_NAME);
+    params.distinguished_name.push(DnType::OrganizationName, "lyrn");
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign, KeyUsagePurpose::DigitalSignature];
+    params.not_before = rcgen::date_time_ymd(2024, 1, 1);
+    params.not_after = rcgen::date_time_ymd(2049, 1, 1);
+    params
+}
+
+fn cert_params(hosts: &[String]) -> Result<CertificateParams, Box<dyn Error>> {
+    let mut params = CertificateParams::new(hosts.to_vec())?;
+    let not_before = Utc::now() - Duration::days(1);
+    let not_after = not_before + Duration::days(CERT_VALIDITY_DAYS);
+    params
+        .distinguished_name
+        .push(DnType::CommonName, hosts.first().cloned().unwrap_or("localhost".into()));
+    params.key_usages = vec![KeyUsagePurpose::DigitalSignature, KeyUsagePurpose::KeyEncipherment];
+    params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+    params.not_before = rcgen::date_time_ymd(not_before.year(), not_before.month() as u8, not_before.day() as u8);
+    params.not_after = rcgen::date_time_ymd(not_after.year(), not_after.month() as u8, not_after.day() as u8);
+    Ok(params)
+}
+
+fn is_fresh(cert: &Path) -> bool {
+    let Ok(modified) = fs::metadata(cert).and_then(|metadata| metadata.modified()) else {
+        return false;
+    };
+    SystemTime::now()
+        .duration_since(modified)
+        .is_ok_and(|age| age.as_secs() < CERT_RENEW_DAYS * 24 * 60 * 60)
+}
+
+/// The CA folder is only accessible by the current user, like mkcert does.
+fn create_private_dir(dir: &Path) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(dir)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+/// Private keys are only readable by the current user.
+fn write_private(path: &Path, content: &str) -> Result<(), Box<dyn Error>> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // The mode only applies to new files, keys written by older versions are 0644
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options.open(path)?.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+fn home_dir() -> Result<PathBuf, Box<dyn Error>> {
+    env::var("HOME")
+        .or(env::var("USERPROFILE"))
+        .map(PathBuf::from)
+        .map_err(|_| "Unable to determine the home directory to store the local certificate authority".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_ensure_in() {
+        let temp_dir = tempdir().unwrap();
+        let ca_dir = temp_dir.path().join("ca");
+        let certs_dir = temp_dir.path().join("certs");
+        let hosts: Vec<String> = vec!["localhost".into(), "127.0.0.1".into(), "myapp.local".into()];
+
+        // The first call creates the certificate authority and the certificate
+        let certificates = ensure_in(&ca_dir, &certs_dir, &hosts).unwrap();
+        assert!(certificates.ca.is_file());
+        assert!(certificates.cert.is_file());
+        assert!(certificates.key.is_file());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&ca_dir), 0o700);
+            assert_eq!(mode(&ca_dir.join("ca-key.pem")), 0o600);
+            assert_eq!(mode(&certificates.key), 0o600);
+        }
+        let ca = fs::read_to_string(&certificates.ca).unwrap();
+        let cert = fs::read_to_string(&certificates.cert).unwrap();
+
+        // The second call with the same hosts reuses the cached files
+        let certificates = ensure_in(&ca_dir, &certs_dir, &hosts).unwrap();
+        assert_eq!(fs::read_to_string(&certificates.ca).unwrap(), ca);
+        assert_eq!(fs::read_to_string(&certificates.cert).unwrap(), cert);
+
+        // A new host issues a new certificate signed by the same authority
+        let hosts: Vec<String> = vec!["localhost".into(), "192.168.0.10".into()];
+        let certificates = ensure_in(&ca_dir, &certs_dir, &hosts).unwrap();
+        assert_eq!(fs::read_to_string(&certificates.ca).unwrap(), ca);
+        assert_ne!(fs::read_to_string(&certificates.cert).unwrap(), cert);
+
+        temp_dir.close().unwrap();
+    }
+}
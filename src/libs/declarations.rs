@@ -0,0 +1,121 @@
+use super::generated;
+use super::output;
+use super::project_config::ProjectConfig;
+use crate::templates::Framework;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+const FILE: &str = "index.d.ts";
+
+/// Module declarations for the asset types the webpack configuration handles.
+pub fn declarations(project_config: &ProjectConfig) -> String {
+    let mut modules: Vec<String> = vec!["css".into()];
+    modules.extend(project_config.assets.image_formats());
+    modules.push("svg".into());
+    let mut content: String = modules.iter().map(|module| format!("declare module '*.{}';\n", module)).collect();
+    if project_config.assets.svg_components {
+        content.push_str(match project_config.app.framework {
+            Framework::None => "",
+            Framework::React => SVG_COMPONENT_REACT,
+            Framework::Vue => SVG_COMPONENT_VUE,
+        });
+    }
+    content
+}
+
+/// Rewrites `index.d.ts` in the source folder when the asset settings change, unless it was edited by hand.
+pub fn sync(project_config: &ProjectConfig) -> Result<(), Box<dyn Error>> {
+    let path = Path::new(&project_config.paths.src).join(FILE);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    let body = declarations(project_config);
+    let generated = generated::stamp(&body);
+    if content == generated {
+        return Ok(());
+    }
+    let is_stock = content.trim().is_empty() || content == LEGACY_CONTENT;
+    if !is_stock && generated::is_modified(&content) {
+        let missing: Vec<&str> = body.lines().filter(|line| line.starts_with("declare") && !content.contains(line)).collect();
+        if !missing.is_empty() {
+            output::warn(&format!(
+                "{} was edited by hand and misses declarations for the asset settings in lyrn.json:",
+                path.display()
+            ));
+            missing.iter().for_each(|line| output::text(&format!("  {}", line)));
+            output::text("Add them by hand, or empty the file to let lyrn regenerate it");
+        }
+        return Ok(());
+    }
+    fs::write(&path, generated)?;
+    output::info(&format!("Updated {} to match the asset settings in lyrn.json", path.display()));
+    Ok(())
+}
+
+/// The content created by lyrn before the file had a header, it is still safe to replace.
+const LEGACY_CONTENT: &str = "declare module '*.css';\ndeclare module '*.png';\ndeclare module '*.jpg';\ndeclare module '*.jpeg';\ndeclare module '*.svg'; \n";
+
+const SVG_COMPONENT_REACT: &str = r###"
+declare module '*.svg?component' {
+  import { type FunctionComponent, type SVGProps } from 'react';
+  const Component: FunctionComponent<SVGProps<SVGSVGElement>>;
+  export default Component;
+}
+"###;
+
+const SVG_COMPONENT_VUE: &str = r###"
+declare module '*.svg?component' {
+  import { type DefineComponent } from 'vue';
+  const Component: DefineComponent;
+  export default Component;
+}
+"###;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_declarations() {
+        let mut project_config = ProjectConfig::default();
+        project_config.app.framework = Framework::React;
+        project_config.assets.images = vec![".bmp".into(), "png".into()];
+
+        let content = declarations(&project_config);
+
+        assert!(content.contains("declare module '*.avif';\ndeclare module '*.bmp';\ndeclare module '*.svg';\n"));
+        assert_eq!(content.matches("'*.png'").count(), 1);
+        assert!(!content.contains("?component"));
+
+        project_config.assets.svg_components = true;
+
+        assert!(declarations(&project_config).contains("declare module '*.svg?component' {"));
+    }
+
+    #[test]
+    fn test_sync() {
+        let temp_dir = tempdir().unwrap();
+        let mut project_config = ProjectConfig::default();
+        project_config.paths.src = temp_dir.path().to_string_lossy().into();
+        let path = temp_dir.path().join(FILE);
+        let generated = generated::stamp(&declarations(&project_config));
+
+        // The stock file of older versions is regenerated
+        fs::write(&path, LEGACY_CONTENT).unwrap();
+        sync(&project_config).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), generated);
+
+        // A file edited by hand is kept
+        let edited = format!("{}declare module '*.txt';\n", generated);
+        fs::write(&path, &edited).unwrap();
+        sync(&project_config).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), edited);
+
+        // An empty file is regenerated
+        fs::write(&path, "").unwrap();
+        sync(&project_config).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), generated);
+    }
+}
@@ -0,0 +1,63 @@
+use sha2::{Digest, Sha256};
+use similar::TextDiff;
+
+const HEADER: &str = "// Generated by lyrn. sha256:";
+
+/// Prepends a header with the hash of `body`, so later edits of the file can be detected.
+pub fn stamp(body: &str) -> String {
+    format!("{}{}\n{}", HEADER, hash(body), body)
+}
+
+/// Whether a generated file was changed after lyrn wrote it. Files without a header count as modified.
+pub fn is_modified(content: &str) -> bool {
+    let (header, body) = content.split_once('\n').unwrap_or((content, ""));
+    match header.strip_prefix(HEADER) {
+        Some(expected) => expected != hash(body),
+        None => true,
+    }
+}
+
+/// Unified diff of two versions of a generated file, ignoring their headers.
+pub fn diff(current: &str, generated: &str, file: &str) -> String {
+    TextDiff::from_lines(body(current), body(generated))
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", file), &format!("b/{}", file))
+        .to_string()
+}
+
+fn body(content: &str) -> &str {
+    match content.starts_with(HEADER) {
+        true => content.split_once('\n').map_or("", |(_, body)| body),
+        false => content,
+    }
+}
+
+fn hash(body: &str) -> String {
+    Sha256::digest(body.as_bytes()).iter().take(8).map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_modified() {
+        let content = stamp("module.exports = {};\n");
+
+        assert!(content.starts_with("// Generated by lyrn. sha256:"));
+        assert!(!is_modified(&content));
+        assert!(is_modified(&content.replace("{}", "{ mode: 'none' }")));
+        assert!(is_modified("module.exports = {};\n"));
+    }
+
+    #[test]
+    fn test_diff() {
+        let diff = diff("const a = 1;\nconst b = 2;\n", &stamp("const a = 1;\nconst b = 3;\n"), "webpack.config.dev.js");
+
+        assert_eq!(
+            diff,
+            "--- a/webpack.config.dev.js\n+++ b/webpack.config.dev.js\n@@ -1,2 +1,2 @@\n const a = 1;\n-const b = 2;\n+const b = 3;\n"
+        );
+    }
+}
@@ -1,7 +1,12 @@
+use super::output;
 use super::types::User;
-use spinners::{Spinner, Spinners};
+use crate::tools::bridge::Problem;
 use std::error::Error;
+use std::path::Path;
 use std::process::Command;
+use std::{env, fs};
+
+const PROJECT_FILES: [&str; 2] = ["lyrn.json", "package.json"];
 
 pub fn get_git_user() -> Result<User, Box<dyn Error>> {
     let user_name = Command::new("git").args(["config", "user.name"]).output()?;
@@ -12,7 +17,20 @@ pub fn get_git_user() -> Result<User, Box<dyn Error>> {
     })
 }
 
+pub fn get_git_commit() -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).output().ok()?;
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    match output.status.success() && !commit.is_empty() {
+        true => Some(commit),
+        false => None,
+    }
+}
+
 pub fn clear_console() -> Result<(), Box<dyn Error>> {
+    if !output::is_interactive() {
+        return Ok(());
+    }
+
     #[cfg(windows)]
     let mut clear_cmd = Command::new("cmd");
     #[cfg(windows)]
@@ -25,9 +43,12 @@ pub fn clear_console() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-pub fn spinner_start(msg: &str) -> Result<Spinner, Box<dyn Error>> {
-    clear_console()?;
-    Ok(Spinner::new(Spinners::Dots12, msg.into()))
+pub fn print_problems(title: &str, problems: &[Problem]) {
+    output::warn(title);
+    for problem in problems {
+        output::text("");
+        output::text(&problem.to_string());
+    }
 }
 
 pub fn convert_bytes(bytes: u64) -> String {
@@ -51,6 +72,36 @@ pub fn is_default<T: Default + PartialEq>(t: &T) -> bool {
     t == &T::default()
 }
 
+pub fn format_delta(delta: i64) -> String {
+    match delta < 0 {
+        true => format!("-{}", convert_bytes(delta.unsigned_abs())),
+        false => format!("+{}", convert_bytes(delta as u64)),
+    }
+}
+
+/// Removes the output folder, returns false when it does not exist.
+pub fn remove_output_dir(dir: &str) -> Result<bool, Box<dyn Error>> {
+    if fs::metadata(dir).is_err() {
+        return Ok(false);
+    }
+    check_output_dir(Path::new(dir), &env::current_dir()?)?;
+    fs::remove_dir_all(dir)?;
+    Ok(true)
+}
+
+/// `paths.output` comes from lyrn.json, a wrong value must not wipe the project or anything above it.
+fn check_output_dir(dir: &Path, cwd: &Path) -> Result<(), Box<dyn Error>> {
+    let dir = cwd.join(dir).canonicalize()?;
+    let cwd = cwd.canonicalize()?;
+    if cwd.starts_with(&dir) {
+        return Err(format!("The output folder {} contains the project, check `paths.output` in lyrn.json", dir.display()).into());
+    }
+    if let Some(file) = PROJECT_FILES.iter().find(|file| dir.join(file).exists()) {
+        return Err(format!("The output folder {} contains {}, check `paths.output` in lyrn.json", dir.display(), file).into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,6 +139,23 @@ mod tests {
         assert!(output.status.success(), "Clearing the console failed");
     }
 
+    #[test]
+    fn test_check_output_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project = temp_dir.path().join("app");
+        fs::create_dir_all(project.join("dist")).unwrap();
+        fs::create_dir_all(project.join("packages/lib")).unwrap();
+        fs::write(project.join("packages/lib/package.json"), "{}").unwrap();
+
+        assert!(check_output_dir(Path::new("dist"), &project).is_ok());
+        assert!(check_output_dir(Path::new(""), &project).is_err());
+        assert!(check_output_dir(Path::new("."), &project).is_err());
+        assert!(check_output_dir(Path::new(".."), &project).is_err());
+        assert!(check_output_dir(Path::new("dist/.."), &project).is_err());
+        assert!(check_output_dir(&project, &project).is_err());
+        assert!(check_output_dir(Path::new("packages/lib"), &project).is_err());
+    }
+
     #[test]
     fn test_convert_bytes_kb() {
         let bytes = 1024;
@@ -1,5 +1,15 @@
+pub mod analyzer;
+pub mod baseline;
+pub mod browserslist;
+pub mod budgets;
+pub mod cache;
+pub mod certificates;
+pub mod declarations;
+pub mod generated;
 pub mod helpers;
+pub mod output;
 pub mod project;
-pub mod project_config;
 pub mod project_aliases;
+pub mod project_config;
+pub mod report;
 pub mod types;
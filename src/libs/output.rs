@@ -0,0 +1,161 @@
+use super::helpers::clear_console;
+use chrono::Local;
+use spinners::{Spinner, Spinners};
+use std::env;
+use std::io::{stdout, IsTerminal};
+use std::sync::OnceLock;
+
+static OUTPUT: OnceLock<Output> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Output {
+    pub interactive: bool,
+    pub level: Level,
+    pub stderr: bool,
+}
+
+impl Output {
+    pub fn new(no_tty: bool, quiet: bool, verbose: bool) -> Self {
+        let is_ci = env::var("CI").is_ok_and(|ci| !ci.is_empty() && ci != "false" && ci != "0");
+        Self {
+            interactive: !no_tty && !is_ci && stdout().is_terminal(),
+            level: match (quiet, verbose) {
+                (true, _) => Level::Quiet,
+                (_, true) => Level::Verbose,
+                _ => Level::Normal,
+            },
+            stderr: false,
+        }
+    }
+}
+
+pub fn init(output: Output) {
+    let _ = OUTPUT.set(output);
+}
+
+pub fn get() -> Output {
+    *OUTPUT.get_or_init(|| Output::new(false, false, false))
+}
+
+pub fn is_interactive() -> bool {
+    get().interactive
+}
+
+pub fn error(msg: &str) {
+    eprintln!("{}", line(msg));
+}
+
+pub fn warn(msg: &str) {
+    emit(&line(msg));
+}
+
+pub fn info(msg: &str) {
+    if get().level >= Level::Normal {
+        emit(&line(msg));
+    }
+}
+
+pub fn debug(msg: &str) {
+    if get().level >= Level::Verbose {
+        emit(&line(msg));
+    }
+}
+
+pub fn print(msg: &str) {
+    if get().level >= Level::Normal {
+        emit(msg);
+    }
+}
+
+pub fn text(msg: &str) {
+    emit(msg);
+}
+
+fn emit(msg: &str) {
+    match get().stderr {
+        true => eprintln!("{}", msg),
+        false => println!("{}", msg),
+    }
+}
+
+fn line(msg: &str) -> String {
+    match is_interactive() {
+        true => msg.to_string(),
+        false => format!("[{}] {}", Local::now().format("%H:%M:%S"), msg),
+    }
+}
+
+pub struct Progress {
+    spinner: Option<Spinner>,
+    msg: String,
+}
+
+impl Progress {
+    pub fn start(msg: &str) -> Self {
+        let output = get();
+        let spinner = match output.interactive && output.level >= Level::Normal {
+            true => {
+                let _ = clear_console();
+                Some(Spinner::new(Spinners::Dots12, msg.into()))
+            }
+            false => {
+                info(msg);
+                None
+            }
+        };
+        Self { spinner, msg: msg.into() }
+    }
+
+    pub fn update(&mut self, msg: &str) {
+        if self.msg == msg {
+            return;
+        }
+        self.msg = msg.into();
+        match self.spinner.as_mut() {
+            Some(spinner) => {
+                spinner.stop();
+                *spinner = Spinner::new(Spinners::Dots12, format!("{:60}", msg));
+            }
+            None => debug(msg),
+        }
+    }
+
+    pub fn stop(mut self) {
+        if let Some(mut spinner) = self.spinner.take() {
+            spinner.stop();
+            let _ = clear_console();
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if let Some(mut spinner) = self.spinner.take() {
+            spinner.stop_with_newline();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_level() {
+        assert_eq!(Output::new(true, false, false).level, Level::Normal);
+        assert_eq!(Output::new(true, true, false).level, Level::Quiet);
+        assert_eq!(Output::new(true, false, true).level, Level::Verbose);
+    }
+
+    #[test]
+    fn test_output_no_tty() {
+        assert!(!Output::new(true, false, false).interactive);
+    }
+}
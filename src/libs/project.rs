@@ -1,7 +1,7 @@
 use super::helpers::get_git_user;
-use super::project_config::PROJECT_CONFIG;
+use super::project_config::{Transpiler, PROJECT_CONFIG};
 use super::types::{Content, Package};
-use crate::libs::helpers::{clear_console, spinner_start};
+use crate::libs::output::{self, Progress};
 use crate::templates::{Framework, ProjectProps, Template};
 use clap::Args;
 use serde_json::json;
@@ -24,6 +24,8 @@ pub struct CreateProjectArgs {
         value_enum
     )]
     framework: Framework,
+    #[arg(long, default_value_t = Transpiler::Ts, value_enum, help = "Loader that compiles TypeScript")]
+    transpiler: Transpiler,
     #[arg(short, long)]
     tool: Option<String>,
     #[arg(short, long)]
@@ -34,6 +36,7 @@ pub fn create_project(args: CreateProjectArgs) -> Result<(), Box<dyn Error>> {
     let project_props = ProjectProps {
         name: args.name,
         framework: args.framework,
+        transpiler: args.transpiler,
         user: get_git_user()?,
     };
     let template = project_props.clone().get_template();
@@ -87,6 +90,7 @@ fn package(project_props: &ProjectProps, template: &Template) -> Package {
         license: "MIT".to_string(),
         dependencies: template.dependencies.to_owned(),
         dev_dependencies: template.dev_dependencies.to_owned(),
+        browserslist: template.browserslist.to_owned(),
     }
 }
 
@@ -97,18 +101,17 @@ fn run_npm_install(name: &String) {
     #[cfg(not(windows))]
     pub const NPM: &'static str = "npm";
 
-    let mut spinner = spinner_start("📦 Installing npm packages...").unwrap();
-    let output = Command::new(NPM).arg("install").output().expect("Failed to execute 'npm install'");
-    spinner.stop();
-    clear_console().unwrap();
+    let progress = Progress::start("📦 Installing npm packages...");
+    let npm_output = Command::new(NPM).arg("install").output().expect("Failed to execute 'npm install'");
+    progress.stop();
 
-    if output.status.success() {
-        println!("npm packages installed successfully 👍");
-        println!("you can go to the project directory \"cd {}\"", name);
-        println!("and run it 🚀 with the \"npm start\" command");
+    if npm_output.status.success() {
+        output::info("npm packages installed successfully 👍");
+        output::info(&format!("you can go to the project directory \"cd {}\"", name));
+        output::info("and run it 🚀 with the \"npm start\" command");
     } else {
-        println!("npm install failed with error:");
-        println!("{}", String::from_utf8_lossy(&output.stderr));
+        output::error("npm install failed with error:");
+        output::error(&String::from_utf8_lossy(&npm_output.stderr));
     }
 }
 
@@ -132,6 +135,7 @@ mod tests {
                 email: "john@example.com".to_string(),
             },
             framework: Framework::None,
+            transpiler: Transpiler::Ts,
         };
 
         // Create a sample Template
@@ -157,6 +161,7 @@ mod tests {
             postcss_config: "postcss_config".to_string(),
             index_d: "index_d".to_string(),
             index: "index".to_string(),
+            browserslist: json!(["defaults"]),
             app: HashMap::new(),
         };
 
@@ -174,6 +179,7 @@ mod tests {
         assert_eq!(result.license, "MIT");
         assert_eq!(result.dependencies, template.dependencies);
         assert_eq!(result.dev_dependencies, template.dev_dependencies);
+        assert_eq!(result.browserslist, template.browserslist);
     }
 
     #[test]
@@ -1,7 +1,15 @@
-use json_value_merge::Merge;
+use super::project_config::PathsConfig;
+use crate::tools::js::Expr;
 use serde::{Deserialize, Serialize};
-use serde_json::{from_str, json, Value};
-use std::{env, error::Error, fs, path::PathBuf, string::String};
+use serde_json::{from_str, Value};
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    string::String,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Aliases {
@@ -12,71 +20,65 @@ pub struct Aliases {
     pub main: String,
 }
 
-impl Aliases {
-    fn iter_mut(&mut self) -> impl Iterator<Item = &mut String> {
-        IntoIterator::into_iter([&mut self.src, &mut self.build, &mut self.public, &mut self.images, &mut self.main])
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectAliases {
     pub aliases: Aliases,
-    pub is_abs_path: bool,
 }
 
 impl ProjectAliases {
-    pub fn get(&mut self) -> Aliases {
-        self.aliases.iter_mut().for_each(|alias_path| {
-            *alias_path = Self::get_path(&alias_path, &self.is_abs_path);
-        });
-        self.to_owned().aliases
-    }
-
-    pub fn get_json(&mut self) -> Value {
-        let mut aliases_json = json!(self.get());
-        if let Ok(tsconfig_paths) = self.ts_config_paths("tsconfig.json") {
-            aliases_json.merge(tsconfig_paths);
+    pub fn new(paths: &PathsConfig) -> Self {
+        Self {
+            aliases: Aliases {
+                src: paths.src.clone(),
+                build: paths.output.clone(),
+                public: paths.public.clone(),
+                images: paths.images.clone(),
+                main: paths.entry.clone(),
+            },
         }
-        aliases_json
     }
 
-    fn get_path(alias_path: &String, is_abs_path: &bool) -> String {
-        match is_abs_path {
-            true => Self::get_abs_path(alias_path),
-            false => format!(">>>path.resolve(cwd, '{}')", alias_path),
-        }
+    pub fn resolve(&self, path: &str) -> Expr {
+        Expr::path(path)
     }
 
-    fn get_abs_path(alias_path: &String) -> String {
-        let cwd = env::current_dir().unwrap();
-        let mut path = PathBuf::from(&cwd);
-        let path_vec: Vec<String> = alias_path.split("/").map(|s| s.to_string()).collect();
-        path.extend(&path_vec);
-        path.to_string_lossy().into_owned()
+    pub fn to_expr(&self) -> Expr {
+        let aliases = &self.aliases;
+        let mut paths: BTreeMap<String, String> = BTreeMap::from([
+            ("src".into(), aliases.src.clone()),
+            ("build".into(), aliases.build.clone()),
+            ("public".into(), aliases.public.clone()),
+            ("images".into(), aliases.images.clone()),
+            ("main".into(), aliases.main.clone()),
+        ]);
+        if let Ok(tsconfig_paths) = Self::ts_config_paths("tsconfig.json") {
+            paths.extend(tsconfig_paths);
+        }
+        Expr::object(paths.into_iter().map(|(alias, path)| (alias, Expr::Path(path))))
     }
 
-    fn ts_config_paths(&mut self, filename: &str) -> Result<Value, Box<dyn Error>> {
-        let mut config_paths = json!({});
+    fn ts_config_paths(filename: &str) -> Result<BTreeMap<String, String>, Box<dyn Error>> {
         let data = fs::read_to_string(PathBuf::from(&filename))?;
         let json: Value = from_str(&data)?;
-        let mut paths: Value = json["compilerOptions"]["paths"].to_owned();
-        if paths.is_null() {
-            paths = json!({});
+        let mut config_paths: BTreeMap<String, String> = BTreeMap::new();
+        for (key, value) in json["compilerOptions"]["paths"].as_object().into_iter().flatten() {
+            if let Some(path) = value[0].as_str() {
+                config_paths.insert(key.replace("/*", ""), path.replace("/*", ""));
+            }
         }
-        paths.as_object().iter().flat_map(|s| s.iter()).for_each(|(key, value)| {
-            let path_str = value[0].as_str().unwrap().to_string().replace("/*", "");
-            config_paths.merge(json!({
-              key.replace("/*", ""):
-              Self::get_path(&path_str, &self.is_abs_path)
-            }))
-        });
-        let extends = &json["extends"];
-        if extends.is_string() {
-            let extends = json["extends"].as_str().unwrap();
-            let extended_paths = self.ts_config_paths(extends)?;
-            config_paths.merge(extended_paths);
+        if let Some(extends) = json["extends"].as_str() {
+            config_paths.extend(Self::ts_config_paths(extends)?);
         }
-
         Ok(config_paths)
     }
 }
+
+pub fn get_abs_path(alias_path: &str) -> String {
+    if Path::new(alias_path).is_absolute() {
+        return alias_path.to_owned();
+    }
+    let cwd = env::current_dir().unwrap();
+    let mut path = PathBuf::from(&cwd);
+    path.extend(alias_path.split('/'));
+    path.to_string_lossy().into_owned()
+}
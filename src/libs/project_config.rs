@@ -2,12 +2,14 @@ use super::helpers::is_default;
 use crate::commands::start::StartArgs;
 use crate::templates::{Framework, ProjectProps};
 use serde::{Deserialize, Serialize};
-use serde_json::{from_str, to_string};
+use serde_json::{from_str, to_string, Value};
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fs::{read_to_string, File};
 
 pub const PROJECT_CONFIG: &str = "lyrn.json";
 
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
 pub enum EnvType {
     Dev,
     Prod,
@@ -21,6 +23,28 @@ pub struct ProjectConfig {
     pub dev: DevConfig,
     #[serde(default)]
     pub prod: ProdConfig,
+    #[serde(default)]
+    pub paths: PathsConfig,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pages: BTreeMap<String, PageConfig>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub html: HtmlConfig,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub budgets: BudgetsConfig,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub optimization: OptimizationConfig,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub assets: AssetsConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub legacy_targets: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub transpiler: Transpiler,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub cache: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub merge: BTreeMap<String, MergeStrategy>,
 }
 
 impl ProjectConfig {
@@ -43,8 +67,14 @@ impl ProjectConfig {
         let mut project_config: Self = from_str(&data).unwrap_or_default();
         if start_args.is_some() {
             let start_args = start_args.clone().unwrap();
-            if start_args.port.is_some() {
-                project_config.dev.port = start_args.port.unwrap()
+            if let Some(port) = start_args.port {
+                project_config.dev.port = port
+            }
+            if let Some(host) = start_args.host {
+                project_config.dev.host = host
+            }
+            if start_args.https {
+                project_config.dev.protocol = "https".into()
             }
         }
         project_config
@@ -57,24 +87,31 @@ impl ProjectConfig {
             title: project_props.name.to_uppercase(),
             framework: project_props.framework,
         };
+        project_config.assets.svg_components = project_props.framework != Framework::None;
+        project_config.targets = vec!["defaults".into()];
+        project_config.transpiler = project_props.transpiler;
         project_config
     }
 
     pub fn default() -> Self {
         Self {
             app: AppConfig { ..Default::default() },
-            dev: DevConfig {
-                public_path: "/".into(),
-                protocol: "http".into(),
-                host: "localhost".into(),
-                port: 8080,
-                ..Default::default()
-            },
+            dev: DevConfig::default(),
             prod: ProdConfig {
                 public_path: "/".into(),
                 ..Default::default()
             },
-            ..Default::default()
+            paths: PathsConfig::default(),
+            pages: BTreeMap::new(),
+            html: HtmlConfig::default(),
+            budgets: BudgetsConfig::default(),
+            optimization: OptimizationConfig::default(),
+            assets: AssetsConfig::default(),
+            targets: vec![],
+            legacy_targets: vec![],
+            transpiler: Transpiler::default(),
+            cache: false,
+            merge: BTreeMap::new(),
         }
     }
 }
@@ -89,18 +126,77 @@ pub struct AppConfig {
     pub framework: Framework,
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DevConfig {
-    #[serde(default, skip_serializing_if = "is_default")]
+    #[serde(skip_serializing_if = "is_default")]
     pub public_path: String,
-    #[serde(default, skip_serializing_if = "is_default")]
+    #[serde(skip_serializing_if = "is_default")]
     pub protocol: String,
-    #[serde(default, skip_serializing_if = "is_default")]
+    #[serde(skip_serializing_if = "is_default")]
     pub host: String,
-    #[serde(default, skip_serializing_if = "is_default")]
+    #[serde(skip_serializing_if = "is_default")]
     pub port: i32,
-    #[serde(default, skip_serializing_if = "is_default")]
+    #[serde(skip_serializing_if = "is_default")]
     pub config: String,
+    #[serde(skip_serializing_if = "is_default")]
+    pub https: HttpsConfig,
+    #[serde(skip_serializing_if = "is_default")]
+    pub proxy: BTreeMap<String, ProxyConfig>,
+}
+
+impl Default for DevConfig {
+    fn default() -> Self {
+        Self {
+            public_path: "/".into(),
+            protocol: "http".into(),
+            host: "localhost".into(),
+            port: 8080,
+            config: String::new(),
+            https: HttpsConfig::default(),
+            proxy: BTreeMap::new(),
+        }
+    }
+}
+
+impl DevConfig {
+    pub fn is_any_host(&self) -> bool {
+        matches!(self.host.as_str(), "0.0.0.0" | "::" | "[::]")
+    }
+
+    pub fn public_host(&self) -> &str {
+        match self.is_any_host() {
+            true => "localhost",
+            false => &self.host,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    pub target: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub path_rewrite: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub ws: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub change_origin: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub secure: Option<bool>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpsConfig {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub key: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub cert: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub ca: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub hosts: Vec<String>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -110,3 +206,216 @@ pub struct ProdConfig {
     #[serde(default, skip_serializing_if = "is_default")]
     pub config: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PathsConfig {
+    pub src: String,
+    pub entry: String,
+    pub output: String,
+    pub public: String,
+    pub images: String,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        Self {
+            src: "src".into(),
+            entry: "src/main.ts".into(),
+            output: "dist".into(),
+            public: "public".into(),
+            images: "src/images".into(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PageConfig {
+    pub entry: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub template: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub chunks: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub filename: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HtmlConfig {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub theme_color: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub open_graph: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub meta: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub favicon: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub template: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub inject: Value,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub script_loading: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub minify: Value,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetsConfig {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub assets: Vec<AssetBudget>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub entrypoints: Vec<EntrypointBudget>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub total: Option<Budget>,
+}
+
+impl BudgetsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty() && self.entrypoints.is_empty() && self.total.is_none()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetBudget {
+    pub pattern: String,
+    #[serde(flatten)]
+    pub budget: Budget,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntrypointBudget {
+    pub name: String,
+    #[serde(flatten)]
+    pub budget: Budget,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub max_size: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub max_increase: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub compression: SizeCompression,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeCompression {
+    #[default]
+    Raw,
+    Gzip,
+    Brotli,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OptimizationConfig {
+    pub minimize: bool,
+    #[serde(skip_serializing_if = "is_default")]
+    pub minifier: Minifier,
+    pub css_minimize: bool,
+    pub split_chunks: bool,
+    pub runtime_chunk: RuntimeChunk,
+    pub module_ids: String,
+}
+
+impl Default for OptimizationConfig {
+    fn default() -> Self {
+        Self {
+            minimize: true,
+            minifier: Minifier::default(),
+            css_minimize: true,
+            split_chunks: true,
+            runtime_chunk: RuntimeChunk::Single,
+            module_ids: "deterministic".into(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Minifier {
+    #[default]
+    Terser,
+    Esbuild,
+    Swc,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssetsConfig {
+    pub inline_limit: u64,
+    #[serde(skip_serializing_if = "is_default")]
+    pub images: Vec<String>,
+    #[serde(skip_serializing_if = "is_default")]
+    pub svg_components: bool,
+}
+
+impl Default for AssetsConfig {
+    fn default() -> Self {
+        Self {
+            inline_limit: 8192,
+            images: vec![],
+            svg_components: false,
+        }
+    }
+}
+
+impl AssetsConfig {
+    pub const IMAGES: [&'static str; 7] = ["ico", "gif", "png", "jpg", "jpeg", "webp", "avif"];
+    pub const FONTS: [&'static str; 5] = ["woff", "woff2", "eot", "ttf", "otf"];
+
+    /// Built-in image formats followed by the extra ones from lyrn.json, without leading dots or duplicates.
+    pub fn image_formats(&self) -> Vec<String> {
+        let mut formats: Vec<String> = Self::IMAGES.iter().map(|format| format.to_string()).collect();
+        for format in &self.images {
+            let format = format.trim_start_matches('.').to_lowercase();
+            // SVG files and fonts have their own rules.
+            let is_reserved = format == "svg" || Self::FONTS.contains(&format.as_str());
+            if !format.is_empty() && !is_reserved && !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+        formats
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+pub enum Transpiler {
+    #[default]
+    #[serde(rename = "ts-loader")]
+    #[value(name = "ts-loader")]
+    Ts,
+    #[serde(rename = "babel-loader")]
+    #[value(name = "babel-loader")]
+    Babel,
+    #[serde(rename = "swc-loader")]
+    #[value(name = "swc-loader")]
+    Swc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    Append,
+    Prepend,
+    Replace,
+    Merge,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeChunk {
+    #[default]
+    Single,
+    Multiple,
+    None,
+}
@@ -0,0 +1,275 @@
+use super::helpers::get_git_commit;
+use super::project_config::SizeCompression;
+use crate::tools::bridge::{Compilation, Problem};
+use brotli::enc::BrotliEncoderParams;
+use flate2::{write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::ops::AddAssign;
+use std::path::Path;
+
+pub const REPORT_SCHEMA: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub schema: u32,
+    pub lyrn_version: String,
+    pub mode: String,
+    pub git_commit: Option<String>,
+    pub hash: String,
+    pub duration: u64,
+    pub assets: Vec<AssetReport>,
+    pub chunks: Vec<ChunkReport>,
+    pub entrypoints: Vec<EntrypointReport>,
+    pub warnings: Vec<ProblemReport>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Sizes {
+    pub size: u64,
+    pub gzip: u64,
+    pub brotli: u64,
+}
+
+impl Sizes {
+    pub fn of(content: &[u8]) -> Self {
+        Self {
+            size: content.len() as u64,
+            gzip: gzip_size(content),
+            brotli: brotli_size(content),
+        }
+    }
+
+    pub fn get(&self, compression: SizeCompression) -> u64 {
+        match compression {
+            SizeCompression::Raw => self.size,
+            SizeCompression::Gzip => self.gzip,
+            SizeCompression::Brotli => self.brotli,
+        }
+    }
+}
+
+impl AddAssign for Sizes {
+    fn add_assign(&mut self, other: Self) {
+        self.size += other.size;
+        self.gzip += other.gzip;
+        self.brotli += other.brotli;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetReport {
+    pub name: String,
+    #[serde(flatten)]
+    pub sizes: Sizes,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChunkReport {
+    pub id: String,
+    pub names: Vec<String>,
+    pub files: Vec<String>,
+    pub size: u64,
+    pub initial: bool,
+    pub entry: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntrypointReport {
+    pub name: String,
+    pub assets: Vec<String>,
+    #[serde(flatten)]
+    pub sizes: Sizes,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProblemReport {
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<u64>,
+    pub column: Option<u64>,
+}
+
+impl From<&Problem> for ProblemReport {
+    fn from(problem: &Problem) -> Self {
+        Self {
+            message: problem.message.clone(),
+            file: problem.file.clone(),
+            line: problem.line,
+            column: problem.column,
+        }
+    }
+}
+
+impl Report {
+    pub fn new(compilation: &Compilation, mode: &str, output_dir: &Path) -> Self {
+        let stats = &compilation.stats;
+        let assets = assets(stats, output_dir);
+        Self {
+            schema: REPORT_SCHEMA,
+            lyrn_version: env!("CARGO_PKG_VERSION").into(),
+            mode: mode.into(),
+            git_commit: get_git_commit(),
+            hash: compilation.hash.clone(),
+            duration: compilation.duration,
+            chunks: chunks(stats),
+            entrypoints: entrypoints(stats, &assets),
+            assets,
+            warnings: compilation.warnings.iter().map(ProblemReport::from).collect(),
+        }
+    }
+
+    pub fn total(&self) -> Sizes {
+        let mut total = Sizes::default();
+        for asset in &self.assets {
+            total += asset.sizes;
+        }
+        total
+    }
+
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_json()? + "\n")?;
+        Ok(())
+    }
+}
+
+fn assets(stats: &Value, output_dir: &Path) -> Vec<AssetReport> {
+    let mut assets: Vec<AssetReport> = array(&stats["assets"])
+        .iter()
+        .map(|asset| {
+            let name = asset["name"].as_str().unwrap_or_default().to_string();
+            match fs::read(output_dir.join(&name)) {
+                Ok(content) => AssetReport {
+                    name,
+                    sizes: Sizes::of(&content),
+                },
+                Err(_) => AssetReport {
+                    name,
+                    sizes: Sizes {
+                        size: asset["size"].as_u64().unwrap_or_default(),
+                        ..Default::default()
+                    },
+                },
+            }
+        })
+        .collect();
+    assets.sort_by(|a, b| a.name.cmp(&b.name));
+    assets
+}
+
+fn chunks(stats: &Value) -> Vec<ChunkReport> {
+    let mut chunks: Vec<ChunkReport> = array(&stats["chunks"])
+        .iter()
+        .map(|chunk| ChunkReport {
+            id: match &chunk["id"] {
+                Value::String(id) => id.clone(),
+                id => id.to_string(),
+            },
+            names: strings(&chunk["names"]),
+            files: strings(&chunk["files"]),
+            size: chunk["size"].as_u64().unwrap_or_default(),
+            initial: chunk["initial"].as_bool().unwrap_or_default(),
+            entry: chunk["entry"].as_bool().unwrap_or_default(),
+        })
+        .collect();
+    chunks.sort_by(|a, b| a.id.cmp(&b.id));
+    chunks
+}
+
+fn entrypoints(stats: &Value, assets: &[AssetReport]) -> Vec<EntrypointReport> {
+    let Some(entrypoints) = stats["entrypoints"].as_object() else {
+        return Vec::new();
+    };
+    entrypoints
+        .iter()
+        .map(|(name, entrypoint)| {
+            let mut report = EntrypointReport {
+                name: name.clone(),
+                assets: array(&entrypoint["assets"])
+                    .iter()
+                    .filter_map(|asset| asset.as_str().or(asset["name"].as_str()))
+                    .map(String::from)
+                    .collect(),
+                ..Default::default()
+            };
+            for asset in assets.iter().filter(|asset| report.assets.contains(&asset.name)) {
+                report.sizes += asset.sizes;
+            }
+            report
+        })
+        .collect()
+}
+
+fn array(value: &Value) -> Vec<Value> {
+    value.as_array().cloned().unwrap_or_default()
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    array(value).iter().filter_map(|item| item.as_str()).map(String::from).collect()
+}
+
+pub fn gzip_size(content: &[u8]) -> u64 {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    match encoder.write_all(content).and_then(|_| encoder.finish()) {
+        Ok(compressed) => compressed.len() as u64,
+        Err(_) => 0,
+    }
+}
+
+pub fn brotli_size(content: &[u8]) -> u64 {
+    let mut compressed = Vec::new();
+    let params = BrotliEncoderParams::default();
+    match brotli::BrotliCompress(&mut &content[..], &mut compressed, &params) {
+        Ok(_) => compressed.len() as u64,
+        Err(_) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_report_new() {
+        let dir = tempdir().unwrap();
+        let content = "console.log('lyrn');".repeat(100);
+        fs::create_dir_all(dir.path().join("js")).unwrap();
+        fs::write(dir.path().join("js/main.js"), &content).unwrap();
+        let compilation = Compilation {
+            hash: "abc".into(),
+            duration: 1200,
+            stats: json!({
+                "assets": [{ "name": "js/main.js", "size": 2000 }, { "name": "index.html", "size": 300 }],
+                "chunks": [{ "id": 179, "names": ["main"], "files": ["js/main.js"], "size": 1900, "initial": true, "entry": true }],
+                "entrypoints": { "main": { "name": "main", "assets": [{ "name": "js/main.js" }] } }
+            }),
+            ..Default::default()
+        };
+        let report = Report::new(&compilation, "production", dir.path());
+
+        assert_eq!(report.schema, REPORT_SCHEMA);
+        assert_eq!(report.mode, "production");
+        assert_eq!(report.assets.len(), 2);
+        assert_eq!(report.assets[0].sizes, Sizes { size: 300, gzip: 0, brotli: 0 });
+        let sizes = report.assets[1].sizes;
+        assert_eq!(sizes.size, content.len() as u64);
+        assert!(sizes.gzip > 0 && sizes.gzip < sizes.size);
+        assert!(sizes.brotli > 0 && sizes.brotli < sizes.size);
+        assert_eq!(report.chunks[0].id, "179");
+        assert_eq!(report.entrypoints[0].assets, vec!["js/main.js".to_string()]);
+        assert_eq!(report.entrypoints[0].sizes, sizes);
+        assert_eq!(report.total().size, sizes.size + 300);
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::error::Error;
+use std::fmt;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct User {
@@ -20,6 +22,8 @@ pub struct Package {
     pub license: String,
     pub dependencies: Value,
     pub dev_dependencies: Value,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub browserslist: Value,
 }
 
 #[derive(Debug, Clone)]
@@ -28,3 +32,23 @@ pub enum Content {
     Val(Value),
     Pkg(Package),
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl ExitError {
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+impl fmt::Display for ExitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ExitError {}
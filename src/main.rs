@@ -4,8 +4,10 @@ mod templates;
 mod tools;
 mod traits;
 use clap::{Parser, Subcommand};
-use commands::{create, start, build, export};
-use std::error::Error;
+use commands::{build, clean, create, eject, export, inspect, start};
+use libs::output::{self, Output};
+use libs::types::ExitError;
+use std::process::exit;
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
@@ -13,6 +15,12 @@ use std::error::Error;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(long, global = true, help = "Plain line-oriented output without spinners and console clearing")]
+    no_tty: bool,
+    #[arg(short, long, global = true, conflicts_with = "verbose", help = "Print only warnings and errors")]
+    quiet: bool,
+    #[arg(short, long, global = true, help = "Print detailed progress")]
+    verbose: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -25,15 +33,35 @@ enum Commands {
     Build(build::BuildArgs),
     #[command(about = "Export configuration files", arg_required_else_help = true)]
     Export(export::ExportArgs),
+    #[command(about = "Print the final webpack configuration, including user overrides")]
+    Inspect(inspect::InspectArgs),
+    #[command(about = "Remove the build cache and the output folder")]
+    Clean(clean::CleanArgs),
+    #[command(about = "Replace lyrn with standalone webpack configs and scripts")]
+    Eject(eject::EjectArgs),
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
+fn main() {
     let cli = Cli::parse();
+    let mut output = Output::new(cli.no_tty, cli.quiet, cli.verbose);
+    if matches!(&cli.command, Commands::Build(args) if args.writes_stdout()) {
+        output.interactive = false;
+        output.stderr = true;
+    }
+    output::init(output);
 
-    match cli.command {
+    let result = match cli.command {
         Commands::Create(args) => create::cmd(args),
         Commands::Start(args) => start::cmd(args),
         Commands::Build(args) => build::cmd(args),
         Commands::Export(args) => export::cmd(args),
+        Commands::Inspect(args) => inspect::cmd(args),
+        Commands::Clean(args) => clean::cmd(args),
+        Commands::Eject(args) => eject::cmd(args),
+    };
+
+    if let Err(err) = result {
+        output::error(&format!("Error: {}", err));
+        exit(err.downcast_ref::<ExitError>().map_or(1, |err| err.code));
     }
 }
@@ -1,21 +1,23 @@
 use super::{ProjectProps, Template};
-use crate::libs::{project_config::ProjectConfig, types::User};
+use crate::libs::{declarations, generated, project_config::ProjectConfig, types::User};
 use chrono::Datelike;
 use serde_json::{json, Value};
 
 pub fn get(project_props: &ProjectProps) -> Template {
+    let project_config = ProjectConfig::create(project_props);
     Template {
         scripts: scripts(),
         dependencies: dependencies(),
         dev_dependencies: dev_dependencies(),
-        project_config: ProjectConfig::create(&project_props),
+        browserslist: json!(project_config.targets),
+        index_d: generated::stamp(&declarations::declarations(&project_config)),
+        project_config,
         tsconfig: tsconfig(),
         eslintrc: eslintrc(),
         readme: readme(&project_props),
         mit_license: mit_license(&project_props.user),
         gitignore: gitignore(),
         postcss_config: postcss_config(),
-        index_d: index_d(),
         index: index(&project_props.name),
         ..Template::default()
     }
@@ -39,9 +41,10 @@ fn dev_dependencies() -> Value {
         "@types/webpack-env": "^1.18.1",
         "@typescript-eslint/eslint-plugin": "^6.0.0",
         "@typescript-eslint/parser": "^6.0.0",
-        "babel-loader": "^9.1.3",
+        "browserslist": "^4.22.1",
         "copy-webpack-plugin": "^11.0.0",
         "css-loader": "^6.8.1",
+        "css-minimizer-webpack-plugin": "^5.0.1",
         "dotenv": "^16.3.1",
         "eslint": "^8.45.0",
         "eslint-import-resolver-alias": "^1.1.2",
@@ -57,7 +60,7 @@ fn dev_dependencies() -> Value {
         "sass-loader": "^13.3.2",
         "serve": "^14.2.0",
         "style-loader": "^3.3.3",
-        "ts-loader": "9.4.4",
+        "terser-webpack-plugin": "^5.3.9",
         "typescript": "^5.1.6",
         "webpack": "^5.89.0",
         "webpack-cli": "^5.1.4",
@@ -247,6 +250,9 @@ dist
 tmp
 /out-tsc
 
+# lyrn certificates and cache
+.lyrn
+
 # dependencies
 node_modules
 
@@ -286,21 +292,9 @@ Thumbs.db
 fn postcss_config() -> String {
     r###"module.exports = {
   plugins: {
-    'postcss-preset-env': {
-      browsers: 'last 2 versions',
-    },
+    'postcss-preset-env': {},
   },
-}; 
-"###
-    .into()
-}
-
-fn index_d() -> String {
-    r###"declare module '*.css';
-declare module '*.png';
-declare module '*.jpg';
-declare module '*.jpeg';
-declare module '*.svg'; 
+};
 "###
     .into()
 }
@@ -313,7 +307,7 @@ fn index(name: &String) -> String {
   <meta charset="utf-8">
   <meta http-equiv="X-UA-Compatible" content="IE=edge">
   <meta name="viewport" content="width=device-width,initial-scale=1.0">
-  <title>{}</title>
+  <title><%= htmlWebpackPlugin.options.title %></title>
 </head>
 <body>
   <noscript>
@@ -324,6 +318,6 @@ fn index(name: &String) -> String {
 </body>
 </html>
 "###,
-        name, name
+        name
     )
 }
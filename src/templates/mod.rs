@@ -1,7 +1,7 @@
 use crate::traits::value_ext::ValueExt;
 use crate::{
     libs::{
-        project_config::ProjectConfig,
+        project_config::{ProjectConfig, Transpiler},
         types::{Content, User},
     },
     tools::webpack::WebpackFrameworkConfig,
@@ -14,6 +14,7 @@ use std::collections::HashMap;
 pub mod common;
 pub mod react;
 pub mod styles;
+pub mod transpiler;
 pub mod vue;
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -25,17 +26,26 @@ pub enum Framework {
 }
 
 impl Framework {
-    pub fn get_webpack_config(&self) -> WebpackFrameworkConfig {
+    pub fn get_webpack_config(&self, project_config: &ProjectConfig) -> WebpackFrameworkConfig {
         match &self {
-            Framework::None => WebpackFrameworkConfig {
-                constants: vec![],
-                plugins: vec![],
-                rules: vec![],
-            },
-            Framework::React => react::get_webpack_config(),
-            Framework::Vue => vue::get_webpack_config(),
+            Framework::None => WebpackFrameworkConfig::default(),
+            Framework::React => react::get_webpack_config(project_config),
+            Framework::Vue => vue::get_webpack_config(project_config),
         }
     }
+
+    pub fn dev_dependencies(&self, transpiler: Transpiler) -> Value {
+        let project_props = ProjectProps {
+            name: String::new(),
+            framework: *self,
+            transpiler,
+            user: User {
+                name: String::new(),
+                email: String::new(),
+            },
+        };
+        project_props.get_template().dev_dependencies
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -52,6 +62,7 @@ pub struct Template {
     pub postcss_config: String,
     pub index_d: String,
     pub index: String,
+    pub browserslist: Value,
     pub app: HashMap<&'static str, Content>,
 }
 
@@ -89,16 +100,22 @@ impl Templates {
 pub struct ProjectProps {
     pub name: String,
     pub framework: Framework,
+    pub transpiler: Transpiler,
     pub user: User,
 }
 
 impl ProjectProps {
     pub fn get_template(self) -> Template {
-        Templates {
+        let mut template = Templates {
             common: common::get(&self),
             react: react::get(&self),
             vue: vue::get(&self),
         }
-        .get(&self.framework)
+        .get(&self.framework);
+        let transpiler = transpiler::get(&self);
+        template.dependencies.merge_default(&transpiler.dependencies);
+        template.dev_dependencies.merge_default(&transpiler.dev_dependencies);
+        template.app.extend(transpiler.app);
+        template
     }
 }
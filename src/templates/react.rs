@@ -1,5 +1,10 @@
 use super::{styles::styles, ProjectProps, Template};
-use crate::{libs::types::Content, tools::webpack::WebpackFrameworkConfig};
+use crate::{
+    libs::{project_config::ProjectConfig, types::Content},
+    tools::js::{Expr, Import},
+    tools::webpack::WebpackFrameworkConfig,
+    tools::webpack_model::{Loader, Plugin, Rule},
+};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
@@ -14,18 +19,27 @@ pub fn get(project: &ProjectProps) -> Template {
     }
 }
 
-pub fn get_webpack_config() -> WebpackFrameworkConfig {
+pub fn get_webpack_config(project_config: &ProjectConfig) -> WebpackFrameworkConfig {
+    let mut rules = vec![];
+    if project_config.assets.svg_components {
+        rules.push(svg_component_rule());
+    }
     WebpackFrameworkConfig {
-        constants: vec![REACT_REFRESH_WEBPACK_PLUGIN_CONST.into()],
         plugins: vec![react_refresh_webpack_plugin()],
-        ..WebpackFrameworkConfig::default()
+        rules,
     }
 }
 
-const REACT_REFRESH_WEBPACK_PLUGIN_CONST: &str = "ReactRefreshWebpackPlugin = require('@pmmmwh/react-refresh-webpack-plugin');";
+fn svg_component_rule() -> Rule {
+    Rule {
+        resource_query: Some(Expr::regex("component", "")),
+        loaders: vec![Loader::new("@svgr/webpack")],
+        ..Rule::new("svg-component", Expr::regex("\\.svg$", "i"))
+    }
+}
 
-fn react_refresh_webpack_plugin() -> String {
-    r###"new ReactRefreshWebpackPlugin()"###.into()
+fn react_refresh_webpack_plugin() -> Plugin {
+    Plugin::new(Import::new("ReactRefreshWebpackPlugin", "@pmmmwh/react-refresh-webpack-plugin"), vec![])
 }
 
 fn dependencies() -> Value {
@@ -40,6 +54,7 @@ fn dependencies() -> Value {
 fn dev_dependencies() -> Value {
     json!({
         "@pmmmwh/react-refresh-webpack-plugin": "^0.5.10",
+        "@svgr/webpack": "^8.1.0",
         "@types/react": "^18.2.7",
         "@types/react-dom": "^18.2.4",
         "eslint-plugin-react": "^7.32.2",
@@ -0,0 +1,112 @@
+use super::{Framework, ProjectProps, Template};
+use crate::libs::{project_config::Transpiler, types::Content};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// core-js version the polyfills are injected for, it must match the installed `core-js` release.
+pub const CORE_JS: &str = "3.33";
+
+pub fn get(project: &ProjectProps) -> Template {
+    Template {
+        dependencies: dependencies(project.transpiler),
+        dev_dependencies: dev_dependencies(project.transpiler, project.framework),
+        app: app(project),
+        ..Template::default()
+    }
+}
+
+fn dependencies(transpiler: Transpiler) -> Value {
+    match transpiler {
+        Transpiler::Ts => Value::Null,
+        Transpiler::Babel | Transpiler::Swc => json!({"core-js": "^3.33.0"}),
+    }
+}
+
+fn dev_dependencies(transpiler: Transpiler, framework: Framework) -> Value {
+    match transpiler {
+        Transpiler::Ts => json!({"ts-loader": "9.4.4"}),
+        Transpiler::Babel => {
+            let mut dev_dependencies = json!({
+                "@babel/core": "^7.23.2",
+                "@babel/preset-env": "^7.23.2",
+                "@babel/preset-typescript": "^7.23.2",
+                "babel-loader": "^9.1.3",
+            });
+            if framework == Framework::React {
+                dev_dependencies["@babel/preset-react"] = json!("^7.22.15");
+            }
+            dev_dependencies
+        }
+        Transpiler::Swc => json!({
+            "@swc/core": "^1.3.95",
+            "swc-loader": "^0.2.3",
+        }),
+    }
+}
+
+fn app(project: &ProjectProps) -> HashMap<&'static str, Content> {
+    match project.transpiler {
+        Transpiler::Ts => HashMap::new(),
+        Transpiler::Babel => HashMap::from([(".babelrc", Content::Val(babelrc(project.framework)))]),
+        Transpiler::Swc => HashMap::from([(".swcrc", Content::Val(swcrc(project.framework)))]),
+    }
+}
+
+/// Babel takes the targets from the `browserslist` in package.json and adds the core-js polyfills the code uses.
+fn babelrc(framework: Framework) -> Value {
+    // Vue passes `<script lang="ts">` blocks with the `.vue` file name.
+    let typescript = match framework {
+        Framework::Vue => json!(["@babel/preset-typescript", { "allExtensions": true }]),
+        _ => json!("@babel/preset-typescript"),
+    };
+    let mut presets = vec![json!(["@babel/preset-env", { "useBuiltIns": "usage", "corejs": CORE_JS }]), typescript];
+    if framework == Framework::React {
+        presets.push(json!("@babel/preset-react"));
+    }
+    json!({ "presets": presets })
+}
+
+/// swc reads the `browserslist` in package.json when `env` has no explicit targets.
+fn swcrc(framework: Framework) -> Value {
+    json!({
+        "$schema": "https://swc.rs/schema.json",
+        "jsc": {
+            "parser": {
+                "syntax": "typescript",
+                "tsx": framework == Framework::React
+            }
+        },
+        "env": {
+            "mode": "usage",
+            "coreJs": CORE_JS
+        },
+        "sourceMaps": true
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::types::User;
+
+    #[test]
+    fn test_babel_template() {
+        let project = ProjectProps {
+            name: "app".into(),
+            framework: Framework::React,
+            transpiler: Transpiler::Babel,
+            user: User {
+                name: String::new(),
+                email: String::new(),
+            },
+        };
+        let template = get(&project);
+        let Some(Content::Val(babelrc)) = template.app.get(".babelrc") else {
+            panic!(".babelrc expected");
+        };
+
+        assert_eq!(babelrc["presets"][2], json!("@babel/preset-react"));
+        assert!(template.dev_dependencies.get("babel-loader").is_some());
+        assert!(template.dev_dependencies.get("ts-loader").is_none());
+    }
+}
@@ -1,5 +1,13 @@
 use super::{styles::styles, ProjectProps, Template};
-use crate::{libs::types::Content, tools::webpack::WebpackFrameworkConfig};
+use crate::{
+    libs::{
+        project_config::{ProjectConfig, Transpiler},
+        types::Content,
+    },
+    tools::js::{Expr, Import},
+    tools::webpack::WebpackFrameworkConfig,
+    tools::webpack_model::{Loader, Plugin, Rule},
+};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
@@ -13,46 +21,56 @@ pub fn get(project: &ProjectProps) -> Template {
     }
 }
 
-pub fn get_webpack_config() -> WebpackFrameworkConfig {
+pub fn get_webpack_config(project_config: &ProjectConfig) -> WebpackFrameworkConfig {
+    let mut rules = vec![vue_rule()];
+    // Babel and swc compile `<script lang="ts">` blocks with the main TypeScript rule.
+    if project_config.transpiler == Transpiler::Ts {
+        rules.push(ts_vue_rule());
+    }
+    if project_config.assets.svg_components {
+        rules.push(svg_component_rule());
+    }
     WebpackFrameworkConfig {
-        constants: vec![VUE_LOADER_PLUGIN_CONST.into()],
         plugins: vec![vue_loader_plugin(), webpack_define_plugin()],
-        rules: vec![vue_rule(), ts_vue_rule()],
+        rules,
     }
 }
 
-const VUE_LOADER_PLUGIN_CONST: &str = "VueLoaderPlugin = require('vue-loader').VueLoaderPlugin;";
+fn vue_loader_plugin() -> Plugin {
+    Plugin::new(Import::member("VueLoaderPlugin", "vue-loader", "VueLoaderPlugin"), vec![])
+}
 
-fn vue_loader_plugin() -> String {
-    r###"new VueLoaderPlugin()"###.into()
+fn webpack_define_plugin() -> Plugin {
+    let definitions = json!({
+        "__VUE_OPTIONS_API__": false,
+        "__VUE_PROD_DEVTOOLS__": false,
+    });
+    Plugin::member(Import::new("webpack", "webpack"), "DefinePlugin", vec![definitions.into()])
 }
 
-fn webpack_define_plugin() -> String {
-    r###"new webpack.DefinePlugin({
-  __VUE_OPTIONS_API__: false,
-  __VUE_PROD_DEVTOOLS__: false,
-})"###
-        .into()
+fn vue_rule() -> Rule {
+    Rule {
+        loaders: vec![Loader::new("vue-loader")],
+        ..Rule::new("vue", Expr::regex("\\.vue$", ""))
+    }
 }
 
-fn vue_rule() -> String {
-    r###"new Object({
-  test: /\.vue$/,
-  loader: 'vue-loader'
-})"###
-        .into()
+fn svg_component_rule() -> Rule {
+    Rule {
+        resource_query: Some(Expr::regex("component", "")),
+        loaders: vec![Loader::new("vue-loader"), Loader::new("vue-svg-loader")],
+        ..Rule::new("svg-component", Expr::regex("\\.svg$", "i"))
+    }
 }
 
-fn ts_vue_rule() -> String {
-    r###"new Object({
-  test: /\.ts$/,
-  loader: 'ts-loader',
-  options: {
-    appendTsSuffixTo: [/\.vue$/],
-    transpileOnly: true
-  }
-})"###
-        .into()
+fn ts_vue_rule() -> Rule {
+    Rule {
+        loaders: vec![Loader {
+            options: Expr::object([("appendTsSuffixTo", Expr::array([Expr::regex("\\.vue$", "")])), ("transpileOnly", true.into())]),
+            ..Loader::new("ts-loader")
+        }],
+        ..Rule::new("vue-typescript", Expr::regex("\\.ts$", ""))
+    }
 }
 
 fn dependencies() -> Value {
@@ -67,6 +85,7 @@ fn dev_dependencies() -> Value {
         "@vue/eslint-config-typescript": "^11.0.3",
         "eslint-plugin-vue": "^9.14.1",
         "vue-loader": "^17.1.1",
+        "vue-svg-loader": "^0.17.0-beta.2",
     })
 }
 
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{from_str, from_value, Value};
+use std::error::Error;
+use std::fmt;
+use std::io::{BufRead, BufReader, Lines, Write};
+use std::process::{Child, ChildStdout, Command, ExitStatus, Stdio};
+
+pub const PROTOCOL_VERSION: u64 = 1;
+pub const SCRIPT: &str = "node_modules/lyrn/tools/webpack.js";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    Progress {
+        percent: u8,
+        #[serde(default)]
+        message: String,
+    },
+    Compile,
+    Done(Compilation),
+    Config {
+        config: Value,
+    },
+    Fatal {
+        message: String,
+        #[serde(default)]
+        stack: String,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Compilation {
+    #[serde(default)]
+    pub hash: String,
+    #[serde(default)]
+    pub duration: u64,
+    #[serde(default)]
+    pub errors: Vec<Problem>,
+    #[serde(default)]
+    pub warnings: Vec<Problem>,
+    #[serde(default)]
+    pub stats: Value,
+    #[serde(default)]
+    pub cache: Option<CacheStats>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Problem {
+    pub message: String,
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub line: Option<u64>,
+    #[serde(default)]
+    pub column: Option<u64>,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.file, self.line, self.column) {
+            (Some(file), Some(line), Some(column)) => writeln!(f, "{}:{}:{}", file, line, column)?,
+            (Some(file), Some(line), None) => writeln!(f, "{}:{}", file, line)?,
+            (Some(file), _, _) => writeln!(f, "{}", file)?,
+            _ => (),
+        }
+        write!(f, "{}", self.message.trim_end())
+    }
+}
+
+pub struct Bridge {
+    child: Child,
+}
+
+impl Bridge {
+    pub fn spawn<T: Serialize>(script: &str, args: &[&str], input: &T) -> Result<Self, Box<dyn Error>> {
+        let mut child = Command::new("node")
+            .arg(script)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("Failed to run node: {}", err))?;
+        let mut child_stdin = child.stdin.take().ok_or("Failed to open stdin for child process")?;
+        child_stdin.write_all(serde_json::to_string(input)?.as_bytes())?;
+        drop(child_stdin);
+        Ok(Self { child })
+    }
+
+    pub fn events(&mut self) -> Result<Events, Box<dyn Error>> {
+        let stdout = self.child.stdout.take().ok_or("Failed to open stdout for child process")?;
+        Ok(Events {
+            lines: BufReader::new(stdout).lines(),
+        })
+    }
+
+    pub fn wait(&mut self) -> Result<ExitStatus, Box<dyn Error>> {
+        Ok(self.child.wait()?)
+    }
+}
+
+pub struct Events {
+    lines: Lines<BufReader<ChildStdout>>,
+}
+
+impl Iterator for Events {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        for line in self.lines.by_ref() {
+            if let Some(event) = parse(&line.ok()?) {
+                return Some(event);
+            }
+        }
+        None
+    }
+}
+
+pub fn parse(line: &str) -> Option<Event> {
+    let message: Value = from_str(line).ok()?;
+    let version = message.get("v")?.as_u64()?;
+    if version != PROTOCOL_VERSION {
+        return Some(Event::Fatal {
+            message: format!(
+                "The lyrn npm package speaks protocol version {}, but this lyrn binary expects version {}. Install the same version of both",
+                version, PROTOCOL_VERSION
+            ),
+            stack: String::new(),
+        });
+    }
+    from_value(message).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_progress() {
+        let line = r#"{"v":1,"type":"progress","percent":42,"message":"building"}"#;
+        let expected_result = Event::Progress {
+            percent: 42,
+            message: "building".into(),
+        };
+
+        assert_eq!(parse(line), Some(expected_result));
+    }
+
+    #[test]
+    fn test_parse_done() {
+        let line = r#"{"v":1,"type":"done","hash":"abc","duration":1200,"errors":[{"message":"Unexpected token","file":"./src/main.ts","line":3,"column":7}],"warnings":[],"stats":{"assets":[]}}"#;
+        let Some(Event::Done(compilation)) = parse(line) else {
+            panic!("done event expected");
+        };
+
+        assert_eq!(compilation.duration, 1200);
+        assert_eq!(compilation.errors.len(), 1);
+        assert_eq!(compilation.errors[0].to_string(), "./src/main.ts:3:7\nUnexpected token");
+        assert_eq!(compilation.stats, json!({"assets": []}));
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let line = r#"{"v":1,"type":"config","config":{"mode":"production"}}"#;
+        let expected_result = Event::Config {
+            config: json!({"mode": "production"}),
+        };
+
+        assert_eq!(parse(line), Some(expected_result));
+    }
+
+    #[test]
+    fn test_parse_ignores_foreign_lines() {
+        assert_eq!(parse("compiled successfully"), None);
+        assert_eq!(parse(r#"{"type":"done"}"#), None);
+    }
+
+    #[test]
+    fn test_parse_unsupported_version() {
+        let line = r#"{"v":2,"type":"compile"}"#;
+
+        assert!(matches!(parse(line), Some(Event::Fatal { .. })));
+    }
+}
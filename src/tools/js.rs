@@ -0,0 +1,555 @@
+use crate::libs::project_aliases::get_abs_path;
+use clap::ValueEnum;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::{json, Number, Value};
+
+const INDENT_SIZE: usize = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Null,
+    Bool(bool),
+    Number(Number),
+    Str(String),
+    Array(Vec<Expr>),
+    Object(Vec<(String, Expr)>),
+    Path(String),
+    Regex(String, String),
+    Ref(String),
+    Raw(String),
+    Call(String, Vec<Expr>),
+    New(String, Vec<Expr>),
+    Require(String),
+}
+
+impl Expr {
+    pub fn object<K: Into<String>>(entries: impl IntoIterator<Item = (K, Expr)>) -> Self {
+        Self::Object(entries.into_iter().map(|(key, value)| (key.into(), value)).collect())
+    }
+
+    pub fn array(items: impl IntoIterator<Item = Expr>) -> Self {
+        Self::Array(items.into_iter().collect())
+    }
+
+    pub fn path(path: &str) -> Self {
+        Self::Path(path.into())
+    }
+
+    pub fn regex(source: &str, flags: &str) -> Self {
+        Self::Regex(source.into(), flags.into())
+    }
+
+    pub fn reference(name: &str) -> Self {
+        Self::Ref(name.into())
+    }
+
+    pub fn raw(code: &str) -> Self {
+        Self::Raw(code.into())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Expr> {
+        match self {
+            Self::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Expr> {
+        match self {
+            Self::Object(entries) => entries.iter_mut().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn insert(&mut self, key: &str, value: Expr) {
+        if let Self::Object(entries) = self {
+            match entries.iter_mut().find(|(k, _)| k == key) {
+                Some(entry) => entry.1 = value,
+                None => entries.push((key.into(), value)),
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn to_js(&self) -> String {
+        Printer.print(self)
+    }
+
+    /// Reads the tagged JSON the bridge sends back, the inverse of `Serialize`. Paths arrive as plain strings.
+    pub fn from_bridge(value: Value) -> Self {
+        let Value::Object(mut map) = value else {
+            return match value {
+                Value::Array(items) => Self::Array(items.into_iter().map(Self::from_bridge).collect()),
+                value => Self::from(value),
+            };
+        };
+        let mut take = |key: &str| map.remove(key);
+        if let Some(Value::String(source)) = take("$regex") {
+            return Self::Regex(source, take("flags").and_then(|flags| flags.as_str().map(String::from)).unwrap_or_default());
+        }
+        for tag in ["$new", "$call"] {
+            if let Some(Value::String(callee)) = take(tag) {
+                let args = match take("args") {
+                    Some(Value::Array(args)) => args.into_iter().map(Self::from_bridge).collect(),
+                    _ => vec![],
+                };
+                return if tag == "$new" { Self::New(callee, args) } else { Self::Call(callee, args) };
+            }
+        }
+        match (take("$ref"), take("$raw"), take("$require")) {
+            (Some(Value::String(name)), _, _) => Self::Ref(name),
+            (_, Some(Value::String(code)), _) => Self::Raw(code),
+            (_, _, Some(Value::String(module))) => Self::Require(module),
+            _ => Self::Object(map.into_iter().map(|(key, value)| (key, Self::from_bridge(value))).collect()),
+        }
+    }
+}
+
+impl From<Value> for Expr {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(value) => Self::Bool(value),
+            Value::Number(value) => Self::Number(value),
+            Value::String(value) => Self::Str(value),
+            Value::Array(items) => Self::Array(items.into_iter().map(Self::from).collect()),
+            Value::Object(map) => Self::Object(map.into_iter().map(|(key, value)| (key, Self::from(value))).collect()),
+        }
+    }
+}
+
+impl From<&str> for Expr {
+    fn from(value: &str) -> Self {
+        Self::Str(value.into())
+    }
+}
+
+impl From<String> for Expr {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<bool> for Expr {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i32> for Expr {
+    fn from(value: i32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+/// Serializes into the JSON the Node bridge rebuilds the configuration from: plain values stay JSON,
+/// paths become absolute and code nodes are tagged objects such as `{"$new": "webpack.DefinePlugin", "args": []}`.
+/// Raw code fails to serialize.
+impl Serialize for Expr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Null => serializer.serialize_unit(),
+            Self::Bool(value) => serializer.serialize_bool(*value),
+            Self::Number(value) => value.serialize(serializer),
+            Self::Str(value) => serializer.serialize_str(value),
+            Self::Path(path) => serializer.serialize_str(&get_abs_path(path)),
+            Self::Array(items) => serializer.collect_seq(items),
+            Self::Object(entries) => serializer.collect_map(entries.iter().map(|(key, value)| (key, value))),
+            Self::Regex(source, flags) => json!({ "$regex": source, "flags": flags }).serialize(serializer),
+            Self::Ref(name) => json!({ "$ref": name }).serialize(serializer),
+            // The bridge does not evaluate code, raw JavaScript is only printed into exported files.
+            Self::Raw(code) => Err(serde::ser::Error::custom(format!("raw JavaScript `{}` can not be sent to the bridge", code))),
+            Self::Require(module) => json!({ "$require": module }).serialize(serializer),
+            Self::Call(callee, args) => tagged("$call", callee, args, serializer),
+            Self::New(callee, args) => tagged("$new", callee, args, serializer),
+        }
+    }
+}
+
+fn tagged<S: Serializer>(tag: &str, callee: &str, args: &[Expr], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry(tag, callee)?;
+    map.serialize_entry("args", args)?;
+    map.end()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Printer;
+
+impl Printer {
+    pub fn print(&self, expr: &Expr) -> String {
+        self.expr(expr, 0)
+    }
+
+    fn expr(&self, expr: &Expr, indent: usize) -> String {
+        match expr {
+            Expr::Null => "null".into(),
+            Expr::Bool(value) => value.to_string(),
+            Expr::Number(value) => value.to_string(),
+            Expr::Str(value) => string(value),
+            Expr::Path(path) => self.expr(
+                &Expr::Call("path.resolve".into(), vec![Expr::reference("cwd"), Expr::Str(path.clone())]),
+                indent,
+            ),
+            Expr::Regex(source, flags) => format!("/{}/{}", source, flags),
+            Expr::Ref(name) => name.clone(),
+            Expr::Array(items) if items.is_empty() => "[]".into(),
+            Expr::Array(items) => {
+                let inner = " ".repeat(indent + INDENT_SIZE);
+                let items: Vec<String> = items.iter().map(|item| format!("{}{}", inner, self.expr(item, indent + INDENT_SIZE))).collect();
+                format!("[\n{}\n{}]", items.join(",\n"), " ".repeat(indent))
+            }
+            Expr::Object(entries) if entries.is_empty() => "{}".into(),
+            Expr::Object(entries) => {
+                let inner = " ".repeat(indent + INDENT_SIZE);
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| format!("{}{}: {}", inner, property(key), self.expr(value, indent + INDENT_SIZE)))
+                    .collect();
+                format!("{{\n{}\n{}}}", entries.join(",\n"), " ".repeat(indent))
+            }
+            Expr::Raw(code) => code.lines().collect::<Vec<&str>>().join(&format!("\n{}", " ".repeat(indent))),
+            Expr::Call(callee, args) => format!("{}({})", callee, self.args(args, indent)),
+            Expr::New(callee, args) => format!("new {}({})", callee, self.args(args, indent)),
+            Expr::Require(module) => format!("require({})", string(module)),
+        }
+    }
+
+    fn args(&self, args: &[Expr], indent: usize) -> String {
+        args.iter().map(|arg| self.expr(arg, indent)).collect::<Vec<String>>().join(", ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Import(Import),
+    Const(String, Expr),
+    Export(Expr),
+    Blank,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModuleFormat {
+    /// CommonJS with `require` and `module.exports`
+    #[default]
+    Cjs,
+    /// ES module with `import` and `export default`
+    Mjs,
+    /// TypeScript module typed with the exported type
+    Ts,
+}
+
+impl ModuleFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Cjs => "js",
+            Self::Mjs => "mjs",
+            Self::Ts => "ts",
+        }
+    }
+
+    pub fn from_file(file: &str) -> Option<Self> {
+        match file.rsplit_once('.')?.1 {
+            "js" | "cjs" => Some(Self::Cjs),
+            "mjs" => Some(Self::Mjs),
+            "ts" => Some(Self::Ts),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Module {
+    pub statements: Vec<Statement>,
+    /// Type-only imports, printed for TypeScript.
+    pub types: Vec<Import>,
+    /// Type of the exported value, printed for TypeScript.
+    pub export_type: Option<String>,
+}
+
+impl Module {
+    pub fn print(&self, format: ModuleFormat) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        if format == ModuleFormat::Ts {
+            lines.extend(self.types.iter().map(|import| match &import.member {
+                Some(member) => format!("import type {{ {} }} from {};", member, string(&import.module)),
+                None => import.print(format),
+            }));
+        }
+        for statement in &self.statements {
+            lines.push(match statement {
+                Statement::Import(import) => import.print(format),
+                Statement::Const(name, value) => format!("const {} = {};", name, value.to_js()),
+                Statement::Export(value) => match (format, &self.export_type) {
+                    (ModuleFormat::Cjs, _) => format!("module.exports = {};", value.to_js()),
+                    (ModuleFormat::Ts, Some(export_type)) => {
+                        format!("const config: {} = {};\n\nexport default config;", export_type, value.to_js())
+                    }
+                    _ => format!("export default {};", value.to_js()),
+                },
+                Statement::Blank => String::new(),
+            });
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Import {
+    pub name: String,
+    pub module: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+}
+
+impl Import {
+    pub fn new(name: &str, module: &str) -> Self {
+        Self {
+            name: name.into(),
+            module: module.into(),
+            member: None,
+        }
+    }
+
+    pub fn member(name: &str, module: &str, member: &str) -> Self {
+        Self {
+            member: Some(member.into()),
+            ..Self::new(name, module)
+        }
+    }
+
+    /// An import run only for its side effects, such as the type augmentations of a module.
+    pub fn side_effect(module: &str) -> Self {
+        Self::new("", module)
+    }
+
+    pub fn is_side_effect(&self) -> bool {
+        self.name.is_empty()
+    }
+
+    pub fn is_builtin(&self) -> bool {
+        self.module.starts_with("node:") || NODE_BUILTINS.contains(&self.module.as_str())
+    }
+
+    fn print(&self, format: ModuleFormat) -> String {
+        let module = string(&self.module);
+        match (format, &self.member) {
+            _ if self.is_side_effect() => match format {
+                ModuleFormat::Cjs => format!("require({});", module),
+                _ => format!("import {};", module),
+            },
+            (ModuleFormat::Cjs, _) => format!("const {} = {};", self.name, self.to_expr().to_js()),
+            (_, None) => format!("import {} from {};", self.name, module),
+            (_, Some(member)) if member == &self.name => format!("import {{ {} }} from {};", member, module),
+            (_, Some(member)) => format!("import {{ {} as {} }} from {};", member, self.name, module),
+        }
+    }
+
+    fn to_expr(&self) -> Expr {
+        let require = Expr::Require(self.module.clone());
+        match &self.member {
+            Some(member) => Expr::Ref(format!("{}.{}", Printer.print(&require), member)),
+            None => require,
+        }
+    }
+}
+
+const NODE_BUILTINS: [&str; 6] = ["path", "fs", "os", "url", "util", "crypto"];
+
+pub fn string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('\'');
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("\\'"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('\'');
+    escaped
+}
+
+fn property(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier =
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    match is_identifier {
+        true => key.into(),
+        false => string(key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print() {
+        let expr = Expr::object([
+            ("mode", "production".into()),
+            ("entry", Expr::array([Expr::path("src/main.ts")])),
+            ("Access-Control-Allow-Origin", "*".into()),
+            ("title", "It's a demo\nwith two lines".into()),
+            (
+                "plugins",
+                Expr::array([Expr::New("HtmlWebpackPlugin".into(), vec![Expr::object([("inject", true.into())])])]),
+            ),
+            ("rules", Expr::array([Expr::raw("new Object({\n  test: /\\.vue$/,\n})")])),
+            ("webpack", Expr::Require("webpack".into())),
+            ("context", Expr::Call("path.join".into(), vec![Expr::raw("__dirname"), "src".into()])),
+            ("empty", Expr::object::<&str>([])),
+        ]);
+        let expected_result = r#"{
+  mode: 'production',
+  entry: [
+    path.resolve(cwd, 'src/main.ts')
+  ],
+  'Access-Control-Allow-Origin': '*',
+  title: 'It\'s a demo\nwith two lines',
+  plugins: [
+    new HtmlWebpackPlugin({
+      inject: true
+    })
+  ],
+  rules: [
+    new Object({
+      test: /\.vue$/,
+    })
+  ],
+  webpack: require('webpack'),
+  context: path.join(__dirname, 'src'),
+  empty: {}
+}"#;
+
+        assert_eq!(expr.to_js(), expected_result);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = json!({
+            "output": { "filename": "js/[name].[contenthash].bundle.js", "clean": true },
+            "devServer": { "port": 8080, "headers": { "Access-Control-Allow-Origin": "*" } },
+            "resolve": { "extensions": [".ts", ".js"] },
+            "devtool": false,
+            "cache": null,
+            "ratio": 0.5
+        });
+
+        assert_eq!(serde_json::to_value(Expr::from(json.clone())).unwrap(), json);
+    }
+
+    #[test]
+    fn test_serialize_for_bridge() {
+        let expr = Expr::object([
+            ("path", Expr::path("/srv/app/dist")),
+            ("test", Expr::regex("\\.svg$", "i")),
+            (
+                "plugins",
+                Expr::array([Expr::New("webpack.DefinePlugin".into(), vec![json!({ "DEBUG": false }).into()])]),
+            ),
+            ("minify", Expr::reference("TerserPlugin.swcMinify")),
+        ]);
+        let expected_result = json!({
+            "path": "/srv/app/dist",
+            "test": { "$regex": "\\.svg$", "flags": "i" },
+            "plugins": [{ "$new": "webpack.DefinePlugin", "args": [{ "DEBUG": false }] }],
+            "minify": { "$ref": "TerserPlugin.swcMinify" },
+        });
+
+        assert_eq!(serde_json::to_value(&expr).unwrap(), expected_result);
+        assert!(serde_json::to_value(Expr::raw("process.cwd()")).is_err());
+    }
+
+    #[test]
+    fn test_from_bridge() {
+        let expr = Expr::object([
+            ("test", Expr::regex("\\.svg$", "i")),
+            (
+                "plugins",
+                Expr::array([Expr::New("HtmlWebpackPlugin".into(), vec![json!({ "inject": true }).into()])]),
+            ),
+            ("minify", Expr::reference("TerserPlugin.swcMinify")),
+        ]);
+        let mut value = serde_json::to_value(&expr).unwrap();
+        value["filename"] = json!({ "$raw": "(pathData) => pathData.chunk.name" });
+
+        let mut expected_result = expr;
+        expected_result.insert("filename", Expr::raw("(pathData) => pathData.chunk.name"));
+        assert_eq!(Expr::from_bridge(value), expected_result);
+    }
+
+    fn module() -> Module {
+        Module {
+            statements: vec![
+                Statement::Import(Import::new("path", "path")),
+                Statement::Import(Import::member("VueLoaderPlugin", "vue-loader", "VueLoaderPlugin")),
+                Statement::Blank,
+                Statement::Export(Expr::object([("mode", "development".into())])),
+            ],
+            types: vec![Import::member("Configuration", "webpack", "Configuration")],
+            export_type: Some("Configuration".into()),
+        }
+    }
+
+    #[test]
+    fn test_module_print() {
+        let expected_result = r#"const path = require('path');
+const VueLoaderPlugin = require('vue-loader').VueLoaderPlugin;
+
+module.exports = {
+  mode: 'development'
+};
+"#;
+
+        assert_eq!(module().print(ModuleFormat::Cjs), expected_result);
+    }
+
+    #[test]
+    fn test_module_print_esm() {
+        let expected_result = r#"import path from 'path';
+import { VueLoaderPlugin } from 'vue-loader';
+
+export default {
+  mode: 'development'
+};
+"#;
+
+        assert_eq!(module().print(ModuleFormat::Mjs), expected_result);
+    }
+
+    #[test]
+    fn test_module_print_ts() {
+        let expected_result = r#"import type { Configuration } from 'webpack';
+import path from 'path';
+import { VueLoaderPlugin } from 'vue-loader';
+
+const config: Configuration = {
+  mode: 'development'
+};
+
+export default config;
+"#;
+
+        assert_eq!(module().print(ModuleFormat::Ts), expected_result);
+    }
+
+    #[test]
+    fn test_side_effect_import() {
+        let import = Import::side_effect("webpack-dev-server");
+
+        assert_eq!(import.print(ModuleFormat::Cjs), "require('webpack-dev-server');");
+        assert_eq!(import.print(ModuleFormat::Mjs), "import 'webpack-dev-server';");
+        assert_eq!(import.print(ModuleFormat::Ts), "import 'webpack-dev-server';");
+    }
+}
@@ -1 +1,4 @@
-pub mod webpack;
\ No newline at end of file
+pub mod bridge;
+pub mod js;
+pub mod webpack;
+pub mod webpack_model;
@@ -1,452 +1,858 @@
 use crate::{
     libs::{
-        project_aliases::{Aliases, ProjectAliases},
-        project_config::{EnvType, ProjectConfig},
+        cache::{self, CACHE_DIR},
+        generated, output,
+        project_aliases::ProjectAliases,
+        project_config::{AssetsConfig, EnvType, HtmlConfig, Minifier, OptimizationConfig, PageConfig, ProjectConfig, RuntimeChunk, Transpiler},
+    },
+    templates::{transpiler::CORE_JS, Framework},
+    tools::{
+        js::{Expr, Import, Module, ModuleFormat, Statement},
+        webpack_model::{sort_imports, Loader, Plugin, Rule},
     },
-    templates::Framework,
 };
-use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use serde_json::{json, Value};
-use std::{error::Error, fs, io::Write, ops::Add, string::String};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs,
+    path::Path,
+    string::String,
+};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
 pub struct WebpackConfig {
     pub project_config: ProjectConfig,
-    pub config: Value,
-    pub constants: Vec<String>,
-    pub plugins: Vec<String>,
-    pub rules: Vec<String>,
+    pub config: Expr,
+    pub imports: Vec<Import>,
+    pub plugins: Vec<Plugin>,
+    pub rules: Vec<Rule>,
+    pub minimizers: Vec<Plugin>,
+    /// ES5 build for browsers without ES modules support, run before this one.
+    pub legacy: Option<Box<WebpackConfig>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct WebpackFrameworkConfig {
-    pub constants: Vec<String>,
-    pub plugins: Vec<String>,
-    pub rules: Vec<String>,
+#[derive(Serialize)]
+struct BridgeInput<'a> {
+    project_config: &'a ProjectConfig,
+    imports: &'a [Import],
+    config: Expr,
+    names: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    legacy: Option<&'a WebpackConfig>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Env {
-    name: &'static str,
-    file: &'static str,
-    config: WebpackConfig,
+/// Names of the generated rules and plugins, in the order they appear in the configuration.
+#[derive(Serialize)]
+struct Names<'a> {
+    rules: Vec<&'a str>,
+    plugins: Vec<&'a str>,
 }
 
-impl Env {
-    fn get_js_config(&mut self) -> Result<Vec<String>, Box<dyn Error>> {
-        let mut imports: Vec<String> = Vec::new();
+impl WebpackConfig {
+    fn new(project_config: &ProjectConfig, config: Expr, rules: Vec<Rule>, plugins: Vec<Plugin>, minimizers: Vec<Plugin>) -> Self {
+        let imports = sort_imports(
+            [Import::new("path", "path")]
+                .into_iter()
+                .chain(rules.iter().flat_map(Rule::imports).cloned())
+                .chain(plugins.iter().chain(&minimizers).map(|plugin| plugin.import.clone())),
+        );
+        Self {
+            project_config: project_config.clone(),
+            config,
+            imports,
+            plugins,
+            rules,
+            minimizers,
+            legacy: None,
+        }
+    }
 
-        for constant in &self.config.constants {
-            imports.push(format!("const {}", constant));
+    /// The configuration object with rules, plugins and minimizers in place.
+    pub fn to_expr(&self) -> Expr {
+        let mut config = self.config.clone();
+        if let Some(module) = config.get_mut("module") {
+            module.insert("rules", Expr::array(self.rules.iter().map(Rule::to_expr)));
         }
-        let rules: Vec<String> = self.config.rules.join(",\n").split("\n").map(|s| s.to_string()).collect();
-        let plugins: Vec<String> = self.config.plugins.join(",\n").split("\n").map(|s| s.to_string()).collect();
-        let js_object: Vec<String> = json_to_js_object(
-            &self.config.config,
-            &vec![
-                InsertLines {
-                    lines: rules,
-                    into: "\"rules\": [%s],",
-                },
-                InsertLines {
-                    lines: plugins,
-                    into: "\"plugins\": [%s],",
-                },
-            ],
-        );
+        config.insert("plugins", Expr::array(self.plugins.iter().map(Plugin::to_expr)));
+        if let Some(optimization) = config.get_mut("optimization").filter(|optimization| optimization.get("minimizer").is_some()) {
+            optimization.insert("minimizer", Expr::array(self.minimizers.iter().map(Plugin::to_expr)));
+        }
+        config
+    }
+
+    /// npm packages the configuration needs: imported modules and loaders.
+    pub fn packages(&self) -> BTreeSet<String> {
+        let modules = self
+            .imports
+            .iter()
+            .filter(|import| !import.is_builtin() && !import.module.starts_with('.'))
+            .map(|import| import.module.as_str());
+        let loaders = self.rules.iter().flat_map(|rule| &rule.loaders).filter_map(|loader| loader.loader.as_str());
+        modules.chain(loaders).map(package_name).collect()
+    }
 
-        Ok(([imports, vec!["".into()], js_object]).concat())
+    /// Names of the generated rules and plugins, overrides address them by these names.
+    pub fn names(&self) -> Value {
+        json!(Names {
+            rules: self.rules.iter().map(|rule| rule.name.as_str()).collect(),
+            plugins: self.plugins.iter().map(Plugin::name).collect(),
+        })
+    }
+
+    pub fn to_module(&self) -> Module {
+        let mut statements: Vec<Statement> = self.imports.iter().cloned().map(Statement::Import).collect();
+        statements.push(Statement::Const("cwd".into(), Expr::raw("process.cwd()")));
+        statements.push(Statement::Blank);
+        let config = self.to_expr();
+        let mut types = vec![Import::member("Configuration", "webpack", "Configuration")];
+        if config.get("devServer").is_some() {
+            types.push(Import::side_effect("webpack-dev-server"));
+        }
+        statements.push(Statement::Export(config));
+        Module {
+            statements,
+            types,
+            export_type: Some("Configuration".into()),
+        }
     }
 }
 
-struct InsertLines {
-    lines: Vec<String>,
-    into: &'static str,
+impl Serialize for WebpackConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BridgeInput {
+            project_config: &self.project_config,
+            imports: &self.imports,
+            config: self.to_expr(),
+            names: self.names(),
+            legacy: self.legacy.as_deref(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WebpackFrameworkConfig {
+    pub plugins: Vec<Plugin>,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Env {
+    name: &'static str,
+    file: String,
+    /// The config file lyrn.json points to, empty when not set.
+    custom: String,
+    config: WebpackConfig,
 }
 
-const INDENT_SIZE: usize = 2;
 const DEV: &str = "Development";
 const PROD: &str = "Production";
-const CONFIG_DEV: &str = "webpack.config.dev.js";
-const CONFIG_PROD: &str = "webpack.config.prod.js";
-const IS_DEV: &str = "isDev = true;";
-const IS_PROD: &str = "isDev = false;";
-const PROCESS_CWD_CONST: &str = "cwd = process.cwd();";
-const PATH_CONST: &str = "path = require('path');";
-const WEBPACK_CONST: &str = "webpack = require('webpack');";
-const FORK_TS_CHECKER_WEBPACK_PLUGIN_CONST: &str = "ForkTsCheckerWebpackPlugin = require('fork-ts-checker-webpack-plugin');";
-const HTML_WEBPACK_PLUGIN_CONST: &str = "HtmlWebpackPlugin = require('html-webpack-plugin');";
-const MINI_CSS_EXTRACT_PLUGIN_CONST: &str = "MiniCssExtractPlugin = require('mini-css-extract-plugin');";
-const COPY_WEBPACK_PLUGIN_CONST: &str = "CopyWebpackPlugin = require('copy-webpack-plugin');";
-
-pub fn get_config_dev(is_abs_path: bool, project_config: &ProjectConfig) -> WebpackConfig {
-    let project_aliases = project_aliases(is_abs_path);
-    let webpack_framework_config = Framework::get_webpack_config(&project_config.app.framework);
-    WebpackConfig {
-        project_config: project_config.clone(),
-        config: config_dev(&project_aliases, &project_config),
-        constants: vec![
-            webpack_framework_config.constants,
-            vec![
-                PATH_CONST.into(),
-                WEBPACK_CONST.into(),
-                FORK_TS_CHECKER_WEBPACK_PLUGIN_CONST.into(),
-                COPY_WEBPACK_PLUGIN_CONST.into(),
-                HTML_WEBPACK_PLUGIN_CONST.into(),
-                PROCESS_CWD_CONST.into(),
-                IS_DEV.into(),
-            ],
-        ]
-        .concat(),
-        plugins: vec![
-            vec![
-                fork_ts_checker_webpack_plugin(),
-                copy_webpack_plugin(&project_aliases),
-                html_webpack_plugin(),
-                hot_module_replacement_plugin(),
-            ],
+const CONFIG_DEV: &str = "webpack.config.dev";
+const CONFIG_PROD: &str = "webpack.config.prod";
+/// Browsers the legacy bundle is transpiled for when `legacy_targets` is not set.
+const LEGACY_TARGETS: &str = "ie 11";
+/// Helpers shipped with the lyrn npm package, the bridge resolves them next to itself.
+pub const TARGETS_MODULE: &str = "lyrn/tools/targets";
+
+pub fn get_config_dev(project_config: &ProjectConfig) -> WebpackConfig {
+    let project_aliases = ProjectAliases::new(&project_config.paths);
+    let webpack_framework_config = Framework::get_webpack_config(&project_config.app.framework, project_config);
+    WebpackConfig::new(
+        project_config,
+        config_dev(&project_aliases, project_config),
+        [rules(project_config, true), webpack_framework_config.rules].concat(),
+        [
+            vec![fork_ts_checker_webpack_plugin(), copy_webpack_plugin(&project_aliases)],
+            html_webpack_plugins(&project_aliases, project_config),
+            vec![hot_module_replacement_plugin()],
             webpack_framework_config.plugins,
         ]
         .concat(),
-        rules: vec![vec![tsx_rule(), style_rule(), images_rule(), inline_rule()], webpack_framework_config.rules].concat(),
-    }
-}
-
-pub fn get_config_prod(is_abs_path: bool, project_config: &ProjectConfig) -> WebpackConfig {
-    let project_aliases = project_aliases(is_abs_path);
-    let webpack_framework_config = Framework::get_webpack_config(&project_config.app.framework);
-    WebpackConfig {
-        project_config: project_config.clone(),
-        config: config_prod(&project_aliases, &project_config),
-        constants: vec![
-            webpack_framework_config.constants,
-            vec![
-                PATH_CONST.into(),
-                WEBPACK_CONST.into(),
-                FORK_TS_CHECKER_WEBPACK_PLUGIN_CONST.into(),
-                COPY_WEBPACK_PLUGIN_CONST.into(),
-                HTML_WEBPACK_PLUGIN_CONST.into(),
-                MINI_CSS_EXTRACT_PLUGIN_CONST.into(),
-                PROCESS_CWD_CONST.into(),
-                IS_PROD.into(),
-            ],
-        ]
-        .concat(),
-        plugins: vec![
-            vec![
-                fork_ts_checker_webpack_plugin(),
-                copy_webpack_plugin(&project_aliases),
-                html_webpack_plugin(),
-                mini_css_extract_plugin(),
-                hot_module_replacement_plugin(),
-            ],
+        vec![],
+    )
+}
+
+pub fn get_config_prod(project_config: &ProjectConfig) -> WebpackConfig {
+    let project_aliases = ProjectAliases::new(&project_config.paths);
+    let webpack_framework_config = Framework::get_webpack_config(&project_config.app.framework, project_config);
+    let mut config = WebpackConfig::new(
+        project_config,
+        config_prod(&project_aliases, project_config),
+        [rules(project_config, false), webpack_framework_config.rules].concat(),
+        [
+            vec![fork_ts_checker_webpack_plugin(), copy_webpack_plugin(&project_aliases)],
+            html_webpack_plugins(&project_aliases, project_config),
+            vec![mini_css_extract_plugin(), hot_module_replacement_plugin()],
             webpack_framework_config.plugins,
         ]
         .concat(),
-        rules: vec![vec![tsx_rule(), style_rule(), images_rule(), inline_rule()], webpack_framework_config.rules].concat(),
+        minimizers(&project_config.optimization, !project_config.targets.is_empty(), false),
+    );
+    if project_config.optimization.minimize && project_config.optimization.minifier == Minifier::Esbuild && !project_config.targets.is_empty() {
+        let imports = [
+            Import::new("browserslist", "browserslist"),
+            Import::member("esbuildTargets", TARGETS_MODULE, "esbuildTargets"),
+        ];
+        config.imports = sort_imports(config.imports.into_iter().chain(imports));
+    }
+    config
+}
+
+/// Production configuration that also builds an ES5 bundle set. The modern scripts are loaded as
+/// `type="module"` and the legacy ones with `nomodule`, so every browser runs only one of them.
+pub fn get_config_legacy(project_config: &ProjectConfig) -> WebpackConfig {
+    let mut config = get_config_prod(project_config);
+    config.config.insert("name", "modern".into());
+    // The output folder is removed before the build, cleaning it here would delete the legacy bundle.
+    if let Some(output) = config.config.get_mut("output") {
+        output.insert("clean", false.into());
+    }
+    for plugin in config.plugins.iter_mut().filter(|plugin| plugin.import.name == "HtmlWebpackPlugin") {
+        if let Some(options) = plugin.args.first_mut() {
+            options.insert("scriptLoading", "module".into());
+        }
     }
+    config.legacy = Some(Box::new(config_legacy(project_config)));
+    config
 }
 
-pub fn show_config(env_type: EnvType) -> Result<(), Box<dyn Error>> {
-    let mut env = get_env(&env_type);
+fn config_legacy(project_config: &ProjectConfig) -> WebpackConfig {
+    let project_aliases = ProjectAliases::new(&project_config.paths);
+    let webpack_framework_config = Framework::get_webpack_config(&project_config.app.framework, project_config);
+    let mut config = config_prod(&project_aliases, project_config);
+    config.insert("name", "legacy".into());
+    config.insert("target", json!(["web", "es5"]).into());
+    if let Some(output) = config.get_mut("output") {
+        output.insert("filename", "js/[name].[contenthash].legacy.js".into());
+        output.insert("chunkFilename", "js/[name].[chunkhash].legacy.chunk.js".into());
+        output.insert("clean", false.into());
+    }
+    if let Some(cache) = config.get_mut("cache") {
+        cache.insert("name", "legacy".into());
+    }
+    let legacy_targets = match project_config.legacy_targets.is_empty() {
+        true => json!(LEGACY_TARGETS),
+        false => json!(project_config.legacy_targets),
+    };
+    let mut rules = [rules(project_config, false), webpack_framework_config.rules].concat();
+    for loader in rules.iter_mut().flat_map(|rule| rule.loaders.iter_mut()) {
+        legacy_loader(loader, &legacy_targets);
+    }
+    rules.extend(dependencies_rule(project_config.transpiler, &legacy_targets));
+    // HTML pages, public files and type checking come from the modern build.
+    WebpackConfig::new(
+        project_config,
+        config,
+        rules,
+        [vec![mini_css_extract_plugin()], webpack_framework_config.plugins].concat(),
+        minimizers(&project_config.optimization, true, true),
+    )
+}
+
+/// Points the transpilers at ES5 browsers and keeps the legacy build from emitting the styles a second time.
+fn legacy_loader(loader: &mut Loader, targets: &Value) {
+    let options = match loader.loader.as_str() {
+        Some("ts-loader") => Expr::object([("compilerOptions", json!({ "target": "es5" }).into())]),
+        Some("babel-loader") => Expr::object([("targets", targets.clone().into())]),
+        Some("swc-loader") => Expr::object([("env", json!({ "targets": targets }).into())]),
+        Some(_) => return,
+        None if loader.import == Some(mini_css_extract_import()) => Expr::object([("emit", false.into())]),
+        None => return,
+    };
+    if loader.options == Expr::Null {
+        loader.options = Expr::object::<&str>([]);
+    }
+    if let Expr::Object(entries) = options {
+        for (key, value) in entries {
+            loader.options.insert(&key, value);
+        }
+    }
+}
+
+/// Dependencies are published as ES2015+ too, babel and swc lower them and add the polyfills they use.
+/// Their own config files are skipped, they describe the TypeScript sources.
+fn dependencies_rule(transpiler: Transpiler, targets: &Value) -> Option<Rule> {
+    let loader = match transpiler {
+        Transpiler::Ts => return None,
+        Transpiler::Babel => Loader::with_options(
+            "babel-loader",
+            json!({
+                "babelrc": false,
+                "configFile": false,
+                "cacheDirectory": true,
+                "sourceType": "unambiguous",
+                "presets": [["@babel/preset-env", { "targets": targets, "useBuiltIns": "usage", "corejs": CORE_JS }]]
+            }),
+        ),
+        Transpiler::Swc => Loader::with_options(
+            "swc-loader",
+            json!({
+                "swcrc": false,
+                "isModule": "unknown",
+                "jsc": { "parser": { "syntax": "ecmascript" } },
+                "env": { "targets": targets, "mode": "usage", "coreJs": CORE_JS }
+            }),
+        ),
+    };
+    Some(Rule {
+        // Polyfills and helpers must not be transpiled, they would import themselves.
+        exclude: Some(Expr::regex(
+            "node_modules[\\\\/](core-js|webpack|@babel[\\\\/]runtime|@swc[\\\\/]helpers)[\\\\/]",
+            "",
+        )),
+        loaders: vec![loader],
+        ..Rule::new("dependencies", Expr::regex("node_modules[\\\\/].+\\.m?js$", ""))
+    })
+}
+
+pub fn show_config(env_type: EnvType, format: ModuleFormat) -> Result<(), Box<dyn Error>> {
+    let env = get_env(&env_type, format);
     println!("\n✅ Webpack {} configuration:\n", env.name);
-    for line in env.get_js_config()? {
-        println!("{}", line);
+    print!("{}", env.config.to_module().print(format));
+    Ok(())
+}
+
+pub fn export_config(env_type: EnvType, format: ModuleFormat) -> Result<(), Box<dyn Error>> {
+    let env = get_env(&env_type, format);
+    fs::write(&env.file, generated::stamp(&env.config.to_module().print(format)))?;
+    let _ = ProjectConfig::get(&None).set_config(&env_type, &env.file).save();
+    output::info(&format!(
+        "✅ Webpack {} configuration has been successfully exported to a file {}",
+        env.name, env.file
+    ));
+    Ok(())
+}
+
+/// Fails when a previously exported file has been edited since lyrn wrote it, including the file lyrn.json
+/// points to when the export switches to another format.
+pub fn check_export(env_type: EnvType, format: ModuleFormat) -> Result<(), Box<dyn Error>> {
+    let env = get_env(&env_type, format);
+    check_files(&env.file, &env.custom)
+}
+
+fn check_files(file: &str, custom: &str) -> Result<(), Box<dyn Error>> {
+    let is_modified = |file: &str| fs::read_to_string(file).is_ok_and(|content| generated::is_modified(&content));
+    if is_modified(file) {
+        return Err(format!(
+            "{} has been modified since it was exported. Run `lyrn export config --diff` to review the changes or use `--force` to overwrite it",
+            file
+        )
+        .into());
+    }
+    if !custom.is_empty() && custom != file && is_modified(custom) {
+        return Err(format!(
+            "{} is used by lyrn.json and has been modified since it was exported, exporting to {} would drop it from the build. Run `lyrn export config --diff` to review the changes or use `--force` to replace it",
+            custom, file
+        )
+        .into());
     }
     Ok(())
 }
 
-pub fn export_config(env_type: EnvType) -> Result<(), Box<dyn Error>> {
-    let mut env = get_env(&env_type);
-    let mut file = fs::File::create(&env.file)?;
-    for line in env.get_js_config()? {
-        file.write_all(format!("{}\n", line).as_bytes())?;
+/// Diffs the file lyrn.json points to in its own format, or the file `format` would be exported to.
+pub fn diff_config(env_type: EnvType, format: ModuleFormat) -> Result<(), Box<dyn Error>> {
+    let env = get_env(&env_type, format);
+    let (file, format) = match ModuleFormat::from_file(&env.custom) {
+        Some(custom_format) => (&env.custom, custom_format),
+        None => (&env.file, format),
+    };
+    let current = fs::read_to_string(file).unwrap_or_default();
+    let diff = generated::diff(&current, &generated::stamp(&env.config.to_module().print(format)), file);
+    match diff.is_empty() {
+        true => output::info(&format!("✅ {} is up to date", file)),
+        false => output::text(diff.trim_end()),
     }
-    let _ = ProjectConfig::get(&None).set_config(&env_type, env.file).save();
-    println!("✅ Webpack {} configuration has been successfully exported to a file {}", env.name, env.file);
     Ok(())
 }
 
-fn get_env(env_type: &EnvType) -> Env {
+fn get_env(env_type: &EnvType, format: ModuleFormat) -> Env {
     let project_config = ProjectConfig::get(&None);
     match env_type {
         EnvType::Dev => Env {
             name: DEV,
-            file: CONFIG_DEV,
-            config: get_config_dev(false, &project_config),
+            file: format!("{}.{}", CONFIG_DEV, format.extension()),
+            custom: project_config.dev.config.clone(),
+            config: get_config_dev(&project_config),
         },
         EnvType::Prod => Env {
             name: PROD,
-            file: CONFIG_PROD,
-            config: get_config_prod(false, &project_config),
+            file: format!("{}.{}", CONFIG_PROD, format.extension()),
+            custom: project_config.prod.config.clone(),
+            config: get_config_prod(&project_config),
         },
     }
 }
 
-fn json_to_js_object(json: &Value, insert_lines: &Vec<InsertLines>) -> Vec<String> {
-    let json_str = serde_json::to_string_pretty(&json).unwrap();
-    let mut lines: Vec<&str> = json_str.split("\n").collect();
-    lines.remove(0);
-    let insert_into_parts: Vec<Vec<&str>> = insert_lines.into_iter().map(|ins| ins.into.split("%s").collect()).collect();
-    let mut new_lines: Vec<String> = Vec::new();
-    new_lines.push("module.exports = {".into());
-    for line in lines.into_iter() {
-        let parent_indent = get_indent_size(line);
-        let key = insert_into_parts.iter().position(|part| line.contains(part[0]));
-        if key.is_some() {
-            let k: usize = key.unwrap();
-            new_lines.push(format_str(insert_into_parts[k][0], &Some(parent_indent)));
-            for insert_line in &insert_lines[k].lines {
-                new_lines.push(format_str(insert_line, &Some(parent_indent.add(INDENT_SIZE))));
+fn package_name(module: &str) -> String {
+    let segments = if module.starts_with('@') { 2 } else { 1 };
+    module.split('/').take(segments).collect::<Vec<&str>>().join("/")
+}
+
+fn resolve_config(project_aliases: &ProjectAliases) -> Expr {
+    Expr::object([
+        (
+            "modules",
+            Expr::array([project_aliases.resolve(&project_aliases.aliases.src), "node_modules".into()]),
+        ),
+        (
+            "extensions",
+            json!([".tsx", ".ts", ".mjs", ".js", ".jsx", ".json", ".wasm", ".css", ".vue"]).into(),
+        ),
+        ("alias", project_aliases.to_expr()),
+    ])
+}
+
+fn config_dev(project_aliases: &ProjectAliases, project_config: &ProjectConfig) -> Expr {
+    let mut dev_server = Expr::object([
+        ("historyApiFallback", history_api_fallback(project_config).into()),
+        ("compress", true.into()),
+        ("host", project_config.dev.host.clone().into()),
+        ("port", project_config.dev.port.into()),
+        ("static", "./".into()),
+        (
+            "headers",
+            json!({
+              "Access-Control-Allow-Origin": "*",
+              "Access-Control-Allow-Methods": "GET, POST, PUT, DELETE, PATCH, OPTIONS",
+              "Access-Control-Allow-Headers": "X-Requested-With, content-type, Authorization",
+            })
+            .into(),
+        ),
+    ]);
+    if project_config.dev.protocol == "https" {
+        dev_server.insert("server", dev_server_https(project_aliases, project_config));
+    }
+    if !project_config.dev.proxy.is_empty() {
+        dev_server.insert("proxy", dev_server_proxy(project_config).into());
+    }
+    Expr::object([
+        ("mode", "development".into()),
+        ("entry", entry(project_aliases, project_config)),
+        (
+            "output",
+            Expr::object([
+                ("path", project_aliases.resolve(&project_aliases.aliases.build)),
+                ("publicPath", dev_public_path(project_config).into()),
+                ("filename", "js/[name].[contenthash].bundle.js".into()),
+                ("assetModuleFilename", "assets/[hash][ext][query]".into()),
+            ]),
+        ),
+        ("resolve", resolve_config(project_aliases)),
+        ("module", Expr::object([("rules", Expr::array([]))])),
+        ("plugins", Expr::array([])),
+        ("devtool", "inline-source-map".into()),
+        ("optimization", json!({ "minimize": false }).into()),
+        ("performance", json!({ "hints": false }).into()),
+        ("cache", cache_config(project_aliases, project_config, "development")),
+        ("target", "web".into()),
+        ("devServer", dev_server),
+        ("infrastructureLogging", json!({ "level": "warn" }).into()),
+        ("stats", json!({ "assets": false, "modules": false }).into()),
+    ])
+}
+
+fn dev_server_proxy(project_config: &ProjectConfig) -> Value {
+    let proxy: serde_json::Map<String, Value> = project_config
+        .dev
+        .proxy
+        .iter()
+        .map(|(context, proxy)| {
+            let mut options = json!({ "target": proxy.target });
+            if !proxy.path_rewrite.is_empty() {
+                options["pathRewrite"] = json!(proxy.path_rewrite);
             }
-            new_lines.push(format_str(insert_into_parts[k][1], &Some(parent_indent)));
-        } else {
-            new_lines.push(format_str(line, &None));
-        }
+            if proxy.ws {
+                options["ws"] = json!(true);
+            }
+            if proxy.change_origin {
+                options["changeOrigin"] = json!(true);
+            }
+            if let Some(secure) = proxy.secure {
+                options["secure"] = json!(secure);
+            }
+            if !proxy.headers.is_empty() {
+                options["headers"] = json!(proxy.headers);
+            }
+            (context.clone(), options)
+        })
+        .collect();
+    Value::Object(proxy)
+}
+
+fn dev_public_path(project_config: &ProjectConfig) -> String {
+    let dev = &project_config.dev;
+    match dev.is_any_host() {
+        true => dev.public_path.clone(),
+        false => format!("{}://{}:{}/", dev.protocol, dev.host, dev.port),
+    }
+}
+
+fn dev_server_https(project_aliases: &ProjectAliases, project_config: &ProjectConfig) -> Expr {
+    let https = &project_config.dev.https;
+    if https.key.is_empty() || https.cert.is_empty() {
+        return "https".into();
+    }
+    let mut options = Expr::object([("key", project_aliases.resolve(&https.key)), ("cert", project_aliases.resolve(&https.cert))]);
+    if !https.ca.is_empty() {
+        options.insert("ca", project_aliases.resolve(&https.ca));
+    }
+    Expr::object([("type", "https".into()), ("options", options)])
+}
+
+fn config_prod(project_aliases: &ProjectAliases, project_config: &ProjectConfig) -> Expr {
+    let mut config = Expr::object([
+        ("mode", "production".into()),
+        ("entry", entry(project_aliases, project_config)),
+        (
+            "output",
+            Expr::object([
+                ("path", project_aliases.resolve(&project_aliases.aliases.build)),
+                ("publicPath", project_config.prod.public_path.clone().into()),
+                ("filename", "js/[name].[contenthash].bundle.js".into()),
+                ("assetModuleFilename", "assets/[hash][ext][query]".into()),
+                ("chunkFilename", "js/[name].[chunkhash].chunk.js".into()),
+                ("clean", true.into()),
+            ]),
+        ),
+        ("resolve", resolve_config(project_aliases)),
+        ("module", Expr::object([("rules", Expr::array([]))])),
+        ("plugins", Expr::array([])),
+        ("stats", "errors-warnings".into()),
+        ("devtool", false.into()),
+        ("optimization", optimization_config(&project_config.optimization)),
+        ("cache", cache_config(project_aliases, project_config, "production")),
+        (
+            "performance",
+            Expr::object([("hints", false.into()), ("maxEntrypointSize", 512000.into()), ("maxAssetSize", 512000.into())]),
+        ),
+    ]);
+    // webpack reads the browsers from package.json, `browserslist` fails when there is no list there.
+    if !project_config.targets.is_empty() {
+        config.insert("target", "browserslist".into());
+    }
+    config
+}
+
+fn cache_config(project_aliases: &ProjectAliases, project_config: &ProjectConfig, name: &str) -> Expr {
+    if !project_config.cache {
+        return false.into();
     }
+    Expr::object([
+        ("type", "filesystem".into()),
+        ("cacheDirectory", project_aliases.resolve(CACHE_DIR)),
+        ("name", name.into()),
+        ("version", cache::version().into()),
+    ])
+}
 
-    new_lines
+fn optimization_config(optimization: &OptimizationConfig) -> Expr {
+    let mut config = Expr::object([
+        ("minimize", optimization.minimize.into()),
+        ("minimizer", Expr::array([])),
+        ("sideEffects", true.into()),
+        ("concatenateModules", true.into()),
+        ("moduleIds", optimization.module_ids.clone().into()),
+        (
+            "runtimeChunk",
+            match optimization.runtime_chunk {
+                RuntimeChunk::Single => "single".into(),
+                RuntimeChunk::Multiple => "multiple".into(),
+                RuntimeChunk::None => false.into(),
+            },
+        ),
+    ]);
+    if optimization.split_chunks {
+        config.insert("splitChunks", Expr::object([("chunks", "all".into())]));
+    }
+    config
 }
 
-fn format_str(line: &str, indent_size: &Option<usize>) -> String {
-    let mut indent = " ".repeat(indent_size.unwrap_or_default());
-    let re = Regex::new(r#"^(\s*)(")?(>{3})?((\w*)([^":]+)?)(")?(:\s)?(")?(>{3})?([^"]*)(")?(,?)$"#).unwrap();
-    let Some(caps) = re.captures(line) else {
-        return format!("{}{}", &indent, &line);
+/// Terser and swc follow the ECMAScript version of webpack's `target`, esbuild needs an explicit one.
+fn minimizers(optimization: &OptimizationConfig, has_targets: bool, is_legacy: bool) -> Vec<Plugin> {
+    if !optimization.minimize {
+        return vec![];
+    }
+    let terser = Import::new("TerserPlugin", "terser-webpack-plugin");
+    let css = Import::new("CssMinimizerPlugin", "css-minimizer-webpack-plugin");
+    let (terser_options, css_options) = match optimization.minifier {
+        Minifier::Terser => (Expr::object([("extractComments", false.into())]), None),
+        Minifier::Esbuild => (
+            esbuild_options(has_targets, is_legacy),
+            Some(Expr::object([("minify", Expr::reference("CssMinimizerPlugin.esbuildMinify"))])),
+        ),
+        Minifier::Swc => (
+            Expr::object([("minify", Expr::reference("TerserPlugin.swcMinify"))]),
+            Some(Expr::object([("minify", Expr::reference("CssMinimizerPlugin.swcMinify"))])),
+        ),
     };
-    indent.push_str(&caps[1]);
-    let key = &caps[4];
-    let val = &caps[11];
-    let comma = &caps[13];
-    let mut quote_key = "";
-    let mut quote_val = "";
-    let mut colon = "";
-    if caps.get(8).is_some() {
-        colon = ": ";
-    }
-    if caps.get(2).is_some() && caps.get(7).is_some() && caps.get(3).is_none() && (caps.get(6).is_some() || caps.get(8).is_none()) {
-        quote_key = "'";
-    }
-    if caps.get(8).is_some() && caps.get(9).is_some() && caps.get(12).is_some() && caps.get(10).is_none() {
-        quote_val = "'";
-    }
-    format!(
-        "{}{}{}{}{}{}{}{}{}",
-        &indent, &quote_key, &key, &quote_key, &colon, &quote_val, &val, &quote_val, &comma
-    )
+    let mut minimizers: Vec<Plugin> = vec![Plugin::new(terser, vec![terser_options])];
+    if optimization.css_minimize {
+        minimizers.push(Plugin::new(css, css_options.into_iter().collect()));
+    }
+    minimizers
 }
 
-fn get_indent_size(line: &str) -> usize {
-    let re = Regex::new(r#"(^\s*)"#).unwrap();
-    let Some(caps) = re.captures(line) else { return 0 };
-    caps[1].len()
+fn esbuild_options(has_targets: bool, is_legacy: bool) -> Expr {
+    let mut options = Expr::object([("minify", Expr::reference("TerserPlugin.esbuildMinify"))]);
+    match (has_targets, is_legacy) {
+        (_, true) => options.insert("terserOptions", json!({ "target": "es5" }).into()),
+        (true, false) => options.insert("terserOptions", Expr::object([("target", esbuild_targets())])),
+        (false, false) => (),
+    }
+    options
 }
 
-fn project_aliases(is_abs_path: bool) -> ProjectAliases {
-    ProjectAliases {
-        aliases: Aliases {
-            src: "src".into(),
-            build: "dist".into(),
-            public: "public".into(),
-            images: "src/images".into(),
-            main: "src/main.ts".into(),
-        },
-        is_abs_path: is_abs_path,
+/// The browserslist of package.json mapped by lyrn's `esbuildTargets` helper.
+fn esbuild_targets() -> Expr {
+    Expr::Call("esbuildTargets".into(), vec![Expr::Call("browserslist".into(), vec![])])
+}
+
+fn fork_ts_checker_webpack_plugin() -> Plugin {
+    Plugin::new(Import::new("ForkTsCheckerWebpackPlugin", "fork-ts-checker-webpack-plugin"), vec![])
+}
+
+fn hot_module_replacement_plugin() -> Plugin {
+    Plugin::member(Import::new("webpack", "webpack"), "HotModuleReplacementPlugin", vec![])
+}
+
+fn html_webpack_plugins(project_aliases: &ProjectAliases, project_config: &ProjectConfig) -> Vec<Plugin> {
+    if project_config.pages.is_empty() {
+        let page = PageConfig {
+            filename: "index.html".into(),
+            ..PageConfig::default()
+        };
+        return vec![html_webpack_plugin(project_aliases, project_config, &page)];
     }
+    pages(project_config)
+        .values()
+        .map(|page| html_webpack_plugin(project_aliases, project_config, page))
+        .collect()
 }
 
-fn config_dev(project_aliases: &ProjectAliases, project_config: &ProjectConfig) -> Value {
-    let aliases = project_aliases.to_owned().get();
-    json!({
-        "mode": "development",
-        "entry": [&aliases.main],
-        "output": {
-          "path": &aliases.build,
-          "publicPath": format!("{}://{}:{}/", project_config.dev.protocol, project_config.dev.host, project_config.dev.port),
-          "filename": "js/[name].[contenthash].bundle.js",
-          "assetModuleFilename": "assets/[hash][ext][query]",
-        },
-        "resolve": {
-          "modules": [&aliases.src, "node_modules"],
-          "extensions": [".tsx", ".ts", ".mjs", ".js", ".jsx", ".json", ".wasm", ".css", ".vue"],
-          "alias": project_aliases.to_owned().get_json(),
-        },
-        "module": {
-          "rules": [],
-        },
-        "plugins": [],
-        "stats": "errors-warnings",
-        "devtool": "inline-source-map",
-        "optimization": {
-          "minimize": false,
-        },
-        "performance": {
-          "hints": false,
-        },
-        "cache": false,
-        "target": "web",
-        "devServer": {
-          "historyApiFallback": true,
-          "compress": true,
-          "port": project_config.dev.port,
-          "static": "./",
-          "headers": {
-            "Access-Control-Allow-Origin": "*",
-            "Access-Control-Allow-Methods": "GET, POST, PUT, DELETE, PATCH, OPTIONS",
-            "Access-Control-Allow-Headers": "X-Requested-With, content-type, Authorization",
-          },
-        },
-        "infrastructureLogging": {
-          "level": "warn",
-        },
-        "stats": {
-          "assets": false,
-          "modules": false,
-        },
-      }
+fn html_webpack_plugin(project_aliases: &ProjectAliases, project_config: &ProjectConfig, page: &PageConfig) -> Plugin {
+    let html = &project_config.html;
+    let aliases = &project_aliases.aliases;
+    let mut options = Expr::object([("filename", page.filename.clone().into())]);
+
+    let title = [&page.title, &html.title, &project_config.app.title, &project_config.app.name]
+        .into_iter()
+        .find(|title| !title.is_empty());
+    if let Some(title) = title {
+        options.insert("title", title.clone().into());
+    }
+    let template = [&page.template, &html.template]
+        .into_iter()
+        .find(|template| !template.is_empty())
+        .cloned()
+        .unwrap_or(format!("{}/index.html", aliases.src));
+    options.insert("template", project_aliases.resolve(&template));
+    let favicon = match html.favicon.is_empty() {
+        true => format!("{}/logo.svg", aliases.images),
+        false => html.favicon.clone(),
+    };
+    if Path::new(&favicon).is_file() {
+        options.insert("favicon", project_aliases.resolve(&favicon));
+    }
+    if !page.chunks.is_empty() {
+        options.insert("chunks", json!(page.chunks).into());
+    }
+    let meta = html_meta(html);
+    if !meta.is_empty() {
+        options.insert("meta", Value::Object(meta).into());
+    }
+    if !html.inject.is_null() {
+        options.insert("inject", html.inject.clone().into());
+    }
+    if !html.script_loading.is_empty() {
+        options.insert("scriptLoading", html.script_loading.clone().into());
+    }
+    if !html.minify.is_null() {
+        options.insert("minify", html.minify.clone().into());
+    }
+
+    Plugin::new(Import::new("HtmlWebpackPlugin", "html-webpack-plugin"), vec![options])
+}
+
+fn html_meta(html: &HtmlConfig) -> serde_json::Map<String, Value> {
+    let mut meta = serde_json::Map::new();
+    if !html.description.is_empty() {
+        meta.insert("description".into(), json!(html.description));
+    }
+    if !html.theme_color.is_empty() {
+        meta.insert("theme-color".into(), json!(html.theme_color));
+    }
+    for (name, content) in &html.open_graph {
+        let property = format!("og:{}", name);
+        meta.insert(property.clone(), json!({ "property": property, "content": content }));
+    }
+    for (name, content) in &html.meta {
+        meta.insert(name.clone(), json!(content));
+    }
+    meta
+}
+
+fn pages(project_config: &ProjectConfig) -> BTreeMap<String, PageConfig> {
+    project_config
+        .pages
+        .iter()
+        .map(|(name, page)| {
+            let mut page = page.clone();
+            if page.chunks.is_empty() {
+                page.chunks = vec![name.clone()];
+            }
+            if page.filename.is_empty() {
+                page.filename = format!("{}.html", name);
+            }
+            (name.clone(), page)
+        })
+        .collect()
+}
+
+fn entry(project_aliases: &ProjectAliases, project_config: &ProjectConfig) -> Expr {
+    if project_config.pages.is_empty() {
+        return Expr::array([project_aliases.resolve(&project_aliases.aliases.main)]);
+    }
+    Expr::object(
+        project_config
+            .pages
+            .iter()
+            .map(|(name, page)| (name.clone(), Expr::array([project_aliases.resolve(&page.entry)]))),
     )
 }
 
-fn config_prod(project_aliases: &ProjectAliases, project_config: &ProjectConfig) -> Value {
-    let aliases = project_aliases.to_owned().get();
-    json!({
-        "mode": "production",
-        "entry": [&aliases.main],
-        "output": {
-          "path": &aliases.build,
-          "publicPath": project_config.prod.public_path,
-          "filename": "js/[name].[contenthash].bundle.js",
-          "assetModuleFilename": "assets/[hash][ext][query]",
-          "chunkFilename": "js/[name].[chunkhash].chunk.js",
-          "clean": true,
-        },
-        "resolve": {
-          "modules": [&aliases.src, "node_modules"],
-          "extensions": [".tsx", ".ts", ".mjs", ".js", ".jsx", ".json", ".wasm", ".css", ".vue"],
-          "alias": project_aliases.to_owned().get_json(),
-        },
-        "module": {
-          "rules": [],
-        },
-        "plugins": [],
-        "stats": "errors-warnings",
-        "devtool": false,
-        "optimization": {
-          "minimize": false,
-          "sideEffects": true,
-          "concatenateModules": true,
-        },
-        "performance": {
-          "hints": false,
-          "maxEntrypointSize": 512000,
-          "maxAssetSize": 512000,
-        },
-      }
+fn history_api_fallback(project_config: &ProjectConfig) -> Value {
+    if project_config.pages.is_empty() {
+        return json!(true);
+    }
+    let pages = pages(project_config);
+    let rewrites: Vec<Value> = pages
+        .iter()
+        .filter(|(_, page)| page.filename != "index.html")
+        .map(|(name, page)| json!({ "from": format!("^/{}(/|$)", regex::escape(name)), "to": format!("/{}", page.filename) }))
+        .collect();
+    let mut fallback = json!({ "rewrites": rewrites });
+    // Other requests get index.html, or the first page when there is none.
+    if !pages.values().any(|page| page.filename == "index.html") {
+        if let Some(page) = pages.values().next() {
+            fallback["index"] = json!(format!("/{}", page.filename));
+        }
+    }
+    fallback
+}
+
+fn mini_css_extract_plugin() -> Plugin {
+    Plugin::new(
+        mini_css_extract_import(),
+        vec![Expr::object([
+            ("filename", "styles/[name].[chunkhash].css".into()),
+            ("chunkFilename", "styles/[name].[chunkhash].chunk.css".into()),
+        ])],
     )
 }
 
-fn tsx_rule() -> String {
-    r###"new Object({
-  test: /\.tsx?$/,
-  exclude: /(node_modules|\.webpack)/,
-  use: {
-    loader: 'ts-loader',
-    options: {
-      transpileOnly: true,
-    },
-  },
-})"###
-        .into()
-}
-
-fn style_rule() -> String {
-    r###"new Object({
-  test: /\.(sass|scss|css)$/,
-  use: [
-    { loader: isDev ? 'style-loader' : MiniCssExtractPlugin.loader },
-    {
-      loader: 'css-loader',
-      options: {
-        importLoaders: isDev ? 1 : 2,
-        sourceMap: isDev,
-      },
-    },
-    { loader: 'postcss-loader', options: { sourceMap: isDev } },
-    { loader: 'sass-loader', options: { sourceMap: isDev } },
-  ],
-})"###
-        .into()
-}
-
-fn images_rule() -> String {
-    r###"new Object({
-  test: /\.(?:ico|gif|png|jpe?g)$/i,
-  type: 'asset/resource',
-  generator: {
-    filename: 'assets/[hash][ext][query]',
-  },
-})"###
-        .into()
-}
-
-fn inline_rule() -> String {
-    r###"new Object({
-  test: /\.(woff(2)?|eot|ttf|otf|svg|)$/i,
-  type: 'asset/inline',
-})"###
-        .into()
-}
-
-fn fork_ts_checker_webpack_plugin() -> String {
-    r###"new ForkTsCheckerWebpackPlugin()"###.into()
-}
-
-fn hot_module_replacement_plugin() -> String {
-    r###"new webpack.HotModuleReplacementPlugin()"###.into()
-}
-
-fn html_webpack_plugin() -> String {
-    r###"new HtmlWebpackPlugin({
-  title: 'APP TITLE',
-  favicon: path.resolve(cwd, './src/images/logo.svg'),
-  template: path.resolve(cwd, './src/index.html'),
-  filename: 'index.html'
-})"###
-        .into()
-}
-
-fn mini_css_extract_plugin() -> String {
-    r###"new MiniCssExtractPlugin({
-  filename: 'styles/[name].[chunkhash].css',
-  chunkFilename: 'styles/[name].[chunkhash].chunk.css',
-})"###
-        .into()
-}
-
-fn copy_webpack_plugin(project_aliases: &ProjectAliases) -> String {
-    format!(
-        r###"new CopyWebpackPlugin({{
-  patterns: [{{
-    from: "{}",
-    to: 'assets',
-    globOptions: {{
-      ignore: ['*.DS_Store'],
-    }},
-    noErrorOnMissing: true, 
-  }}],
-}})"###,
-        project_aliases.to_owned().get().public
+fn mini_css_extract_import() -> Import {
+    Import::new("MiniCssExtractPlugin", "mini-css-extract-plugin")
+}
+
+fn copy_webpack_plugin(project_aliases: &ProjectAliases) -> Plugin {
+    let pattern = Expr::object([
+        ("from", project_aliases.resolve(&project_aliases.aliases.public)),
+        ("to", "assets".into()),
+        ("globOptions", json!({ "ignore": ["*.DS_Store"] }).into()),
+        ("noErrorOnMissing", true.into()),
+    ]);
+    Plugin::new(
+        Import::new("CopyWebpackPlugin", "copy-webpack-plugin"),
+        vec![Expr::object([("patterns", Expr::array([pattern]))])],
     )
 }
 
+fn rules(project_config: &ProjectConfig, is_dev: bool) -> Vec<Rule> {
+    vec![
+        tsx_rule(project_config.transpiler),
+        style_rule(is_dev),
+        images_rule(&project_config.assets),
+        svg_rule(project_config),
+        fonts_rule(&project_config.assets),
+    ]
+}
+
+/// Type checking is left to `ForkTsCheckerWebpackPlugin`, the loaders only strip the types.
+fn tsx_rule(transpiler: Transpiler) -> Rule {
+    let loader = match transpiler {
+        Transpiler::Ts => Loader::with_options("ts-loader", json!({ "transpileOnly": true })),
+        Transpiler::Babel => Loader::with_options("babel-loader", json!({ "cacheDirectory": true })),
+        Transpiler::Swc => Loader::new("swc-loader"),
+    };
+    Rule {
+        exclude: Some(Expr::regex("(node_modules|\\.webpack)", "")),
+        loaders: vec![loader],
+        ..Rule::new("typescript", Expr::regex("\\.tsx?$", ""))
+    }
+}
+
+fn style_rule(is_dev: bool) -> Rule {
+    let style_loader = match is_dev {
+        true => Loader::new("style-loader"),
+        false => Loader::member(mini_css_extract_import(), "loader"),
+    };
+    Rule {
+        loaders: vec![
+            style_loader,
+            Loader::with_options("css-loader", json!({ "importLoaders": if is_dev { 1 } else { 2 }, "sourceMap": is_dev })),
+            Loader::with_options("postcss-loader", json!({ "sourceMap": is_dev })),
+            Loader::with_options("sass-loader", json!({ "sourceMap": is_dev })),
+        ],
+        ..Rule::new("styles", Expr::regex("\\.(sass|scss|css)$", ""))
+    }
+}
+
+fn images_rule(assets: &AssetsConfig) -> Rule {
+    Rule {
+        generator: json!({ "filename": "assets/[hash][ext][query]" }),
+        ..asset_rule("images", extensions_regex(&assets.image_formats()), assets)
+    }
+}
+
+/// SVG files imported with `?component` are left to the framework's SVG component rule.
+fn svg_rule(project_config: &ProjectConfig) -> Rule {
+    let assets = &project_config.assets;
+    let has_components = assets.svg_components && project_config.app.framework != Framework::None;
+    Rule {
+        resource_query: has_components.then(|| Expr::object([("not", Expr::array([Expr::regex("component", "")]))])),
+        ..asset_rule("svg", Expr::regex("\\.svg$", "i"), assets)
+    }
+}
+
+fn fonts_rule(assets: &AssetsConfig) -> Rule {
+    asset_rule("fonts", extensions_regex(&AssetsConfig::FONTS), assets)
+}
+
+/// Matches file names ending with one of the extensions, in any case.
+fn extensions_regex<S: AsRef<str>>(extensions: &[S]) -> Expr {
+    let extensions: Vec<String> = extensions.iter().map(|extension| regex::escape(extension.as_ref())).collect();
+    Expr::regex(&format!("\\.(?:{})$", extensions.join("|")), "i")
+}
+
+/// Files smaller than `inline_limit` are inlined as data URLs, larger ones are emitted to the assets folder.
+fn asset_rule(name: &str, test: Expr, assets: &AssetsConfig) -> Rule {
+    Rule {
+        asset: Some("asset".into()),
+        parser: json!({ "dataUrlCondition": { "maxSize": assets.inline_limit } }),
+        ..Rule::new(name, test)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
 
     fn test_export_config(env_type: EnvType, file_name: &str) {
-        let result = export_config(env_type);
+        let file_name = &format!("{}.js", file_name);
+        let result = export_config(env_type, ModuleFormat::Cjs);
         // Assert that the function returns a valid Result
         assert!(result.is_ok());
         // Check if the expected file was created
@@ -474,44 +880,147 @@ mod tests {
     }
 
     #[test]
-    fn test_json_to_js_object() {
-        let json: Value = json!({
-          "key1": "value1",
-          "key2": "value2",
-          "key3": {},
-        });
-        let lines = vec!["line1: \"inserted1\",".to_string(), "line2: \"inserted2\",".to_string()];
-        let into = "\"key3\": {%s},";
-        let expected_result: Vec<String> = r#"module.exports = {
-  key1: 'value1',
-  key2: 'value2',
-  key3: {
-    line1: 'inserted1',
-    line2: 'inserted2',
-  },
-}"#
-        .split("\n")
-        .into_iter()
-        .map(|s| s.into())
-        .collect();
+    fn test_check_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = |file: &str| temp_dir.path().join(file).to_string_lossy().to_string();
+        let (js, ts) = (path("webpack.config.prod.js"), path("webpack.config.prod.ts"));
+
+        fs::write(&ts, generated::stamp("export default {};\n")).unwrap();
+        assert!(check_files(&js, &ts).is_ok());
+
+        fs::write(&ts, generated::stamp("export default {};\n").replace("{}", "{ mode: 'none' }")).unwrap();
+        assert!(check_files(&ts, &ts).is_err());
+        assert!(check_files(&js, &ts).is_err());
+        assert!(check_files(&js, "").is_ok());
+    }
+
+    #[test]
+    fn test_optimization_disabled() {
+        let optimization = OptimizationConfig {
+            minimize: false,
+            split_chunks: false,
+            runtime_chunk: RuntimeChunk::None,
+            ..Default::default()
+        };
+        let config = optimization_config(&optimization);
 
-        assert_eq!(json_to_js_object(&json, &vec![InsertLines { lines: lines, into: into }]), expected_result);
+        assert_eq!(config.get("minimize"), Some(&Expr::Bool(false)));
+        assert_eq!(config.get("runtimeChunk"), Some(&Expr::Bool(false)));
+        assert!(config.get("splitChunks").is_none());
+        assert!(minimizers(&optimization, false, false).is_empty());
     }
 
     #[test]
-    fn test_format_str() {
-        let line = "\"key\": \"value\"";
-        let indent = Some(2);
-        let expected_result = "  key: 'value'";
+    fn test_cache_config() {
+        let mut project_config = ProjectConfig::default();
+        let project_aliases = ProjectAliases::new(&project_config.paths);
 
-        assert_eq!(format_str(line, &indent), expected_result);
+        assert_eq!(cache_config(&project_aliases, &project_config, "production"), Expr::Bool(false));
+
+        project_config.cache = true;
+        let config = cache_config(&project_aliases, &project_config, "production");
+
+        assert_eq!(config.get("type").and_then(Expr::as_str), Some("filesystem"));
+        assert_eq!(config.get("cacheDirectory"), Some(&Expr::path(".lyrn/cache")));
+        assert_eq!(config.get("version").and_then(Expr::as_str).map(str::len), Some(16));
+    }
+
+    #[test]
+    fn test_legacy_config() {
+        let project_config = ProjectConfig {
+            targets: vec!["defaults".into()],
+            ..ProjectConfig::default()
+        };
+        let config = get_config_legacy(&project_config);
+        let legacy = config.legacy.as_ref().unwrap();
+        let script_loading = config.plugins.iter().find(|plugin| plugin.name() == "HtmlWebpackPlugin").unwrap().args[0].get("scriptLoading");
+
+        assert_eq!(config.config.get("target").and_then(Expr::as_str), Some("browserslist"));
+        assert_eq!(script_loading.and_then(Expr::as_str), Some("module"));
+        assert_eq!(legacy.config.get("target"), Some(&json!(["web", "es5"]).into()));
+        assert_eq!(
+            legacy.rules[0].loaders[0].options.get("compilerOptions"),
+            Some(&json!({ "target": "es5" }).into())
+        );
+        assert!(legacy.plugins.iter().all(|plugin| plugin.name() != "HtmlWebpackPlugin"));
+        assert!(legacy.rules.iter().all(|rule| rule.name != "dependencies"));
+
+        let project_config = ProjectConfig {
+            transpiler: Transpiler::Babel,
+            legacy_targets: vec!["chrome 49".into()],
+            ..project_config
+        };
+        let legacy = config_legacy(&project_config);
+        let dependencies = legacy.rules.iter().find(|rule| rule.name == "dependencies").unwrap();
+
+        assert_eq!(legacy.rules[0].loaders[0].options.get("targets"), Some(&json!(["chrome 49"]).into()));
+        assert_eq!(dependencies.loaders[0].loader.as_str(), Some("babel-loader"));
+        assert_eq!(dependencies.loaders[0].options.get("configFile"), Some(&false.into()));
+    }
+
+    #[test]
+    fn test_esbuild_targets() {
+        let mut project_config = ProjectConfig {
+            targets: vec!["defaults".into()],
+            ..ProjectConfig::default()
+        };
+        project_config.optimization.minifier = Minifier::Esbuild;
+        let config = get_config_prod(&project_config);
+
+        assert!(config.imports.contains(&Import::new("browserslist", "browserslist")));
+        assert!(config.imports.iter().any(|import| import.module == TARGETS_MODULE));
+        assert_eq!(
+            config.minimizers[0].args[0].get("terserOptions"),
+            Some(&Expr::object([("target", esbuild_targets())]))
+        );
+
+        project_config.targets = vec![];
+
+        assert!(!get_config_prod(&project_config).imports.iter().any(|import| import.name == "browserslist"));
     }
 
     #[test]
-    fn test_get_indent_size() {
-        let line = "  some text";
-        let expected_result = 2;
+    fn test_history_api_fallback() {
+        let page = |filename: &str| PageConfig {
+            filename: filename.into(),
+            ..PageConfig::default()
+        };
+        let mut project_config = ProjectConfig::default();
+        project_config.pages.insert("admin.v2".into(), page(""));
+        project_config.pages.insert("shop".into(), page("store.html"));
+
+        assert_eq!(
+            history_api_fallback(&project_config),
+            json!({
+                "rewrites": [
+                    { "from": "^/admin\\.v2(/|$)", "to": "/admin.v2.html" },
+                    { "from": "^/shop(/|$)", "to": "/store.html" }
+                ],
+                "index": "/admin.v2.html"
+            })
+        );
+
+        project_config.pages.insert("home".into(), page("index.html"));
+
+        assert!(history_api_fallback(&project_config).get("index").is_none());
+    }
+
+    #[test]
+    fn test_asset_rules() {
+        let mut project_config = ProjectConfig::default();
+        project_config.assets.images = vec!["bmp".into(), "svg".into(), ".woff2".into(), "tar.gz".into()];
+
+        assert_eq!(
+            images_rule(&project_config.assets).test,
+            Expr::regex("\\.(?:ico|gif|png|jpg|jpeg|webp|avif|bmp|tar\\.gz)$", "i")
+        );
+        assert!(svg_rule(&project_config).resource_query.is_none());
+
+        project_config.app.framework = Framework::Vue;
+        project_config.assets.svg_components = true;
+        let rules = get_config_dev(&project_config).rules;
 
-        assert_eq!(get_indent_size(line), expected_result);
+        assert!(svg_rule(&project_config).resource_query.is_some());
+        assert!(rules.iter().any(|rule| rule.name == "svg-component"));
     }
 }
@@ -0,0 +1,209 @@
+use crate::{
+    libs::output,
+    tools::js::{Expr, Import},
+};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loader {
+    pub loader: Expr,
+    pub options: Expr,
+    pub import: Option<Import>,
+}
+
+impl Loader {
+    pub fn new(loader: &str) -> Self {
+        Self {
+            loader: loader.into(),
+            options: Expr::Null,
+            import: None,
+        }
+    }
+
+    pub fn with_options(loader: &str, options: Value) -> Self {
+        Self {
+            options: options.into(),
+            ..Self::new(loader)
+        }
+    }
+
+    /// A loader exported by a plugin package, e.g. `MiniCssExtractPlugin.loader`.
+    pub fn member(import: Import, member: &str) -> Self {
+        Self {
+            loader: Expr::reference(&format!("{}.{}", import.name, member)),
+            options: Expr::Null,
+            import: Some(import),
+        }
+    }
+
+    pub fn to_expr(&self) -> Expr {
+        let mut loader = Expr::object([("loader", self.loader.clone())]);
+        if self.options != Expr::Null {
+            loader.insert("options", self.options.clone());
+        }
+        loader
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    /// Name the rule can be addressed by in user overrides.
+    pub name: String,
+    pub test: Expr,
+    pub exclude: Option<Expr>,
+    pub resource_query: Option<Expr>,
+    pub loaders: Vec<Loader>,
+    pub asset: Option<String>,
+    pub parser: Value,
+    pub generator: Value,
+}
+
+impl Rule {
+    pub fn new(name: &str, test: Expr) -> Self {
+        Self {
+            name: name.into(),
+            test,
+            exclude: None,
+            resource_query: None,
+            loaders: vec![],
+            asset: None,
+            parser: Value::Null,
+            generator: Value::Null,
+        }
+    }
+
+    pub fn imports(&self) -> impl Iterator<Item = &Import> {
+        self.loaders.iter().filter_map(|loader| loader.import.as_ref())
+    }
+
+    pub fn to_expr(&self) -> Expr {
+        let mut rule = Expr::object([("test", self.test.clone())]);
+        if let Some(exclude) = &self.exclude {
+            rule.insert("exclude", exclude.clone());
+        }
+        if let Some(resource_query) = &self.resource_query {
+            rule.insert("resourceQuery", resource_query.clone());
+        }
+        if !self.loaders.is_empty() {
+            rule.insert("use", Expr::array(self.loaders.iter().map(Loader::to_expr)));
+        }
+        if let Some(asset) = &self.asset {
+            rule.insert("type", asset.as_str().into());
+        }
+        if !self.parser.is_null() {
+            rule.insert("parser", self.parser.clone().into());
+        }
+        if !self.generator.is_null() {
+            rule.insert("generator", self.generator.clone().into());
+        }
+        rule
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plugin {
+    pub import: Import,
+    pub member: Option<String>,
+    pub args: Vec<Expr>,
+}
+
+impl Plugin {
+    pub fn new(import: Import, args: Vec<Expr>) -> Self {
+        Self { import, member: None, args }
+    }
+
+    /// A plugin class exported as a property of a package, e.g. `webpack.DefinePlugin`.
+    pub fn member(import: Import, member: &str, args: Vec<Expr>) -> Self {
+        Self {
+            member: Some(member.into()),
+            ..Self::new(import, args)
+        }
+    }
+
+    pub fn to_expr(&self) -> Expr {
+        let constructor = match &self.member {
+            Some(member) => format!("{}.{}", self.import.name, member),
+            None => self.import.name.clone(),
+        };
+        Expr::New(constructor, self.args.clone())
+    }
+
+    /// Name the plugin can be addressed by in user overrides: its class name.
+    pub fn name(&self) -> &str {
+        self.member.as_deref().unwrap_or(&self.import.name)
+    }
+}
+
+/// Removes duplicate imports by name and orders them: Node built-ins first, then packages by module name.
+pub fn sort_imports(imports: impl IntoIterator<Item = Import>) -> Vec<Import> {
+    let mut unique: Vec<Import> = Vec::new();
+    for import in imports {
+        match unique.iter().find(|existing| existing.name == import.name) {
+            Some(existing) if existing != &import => output::warn(&format!(
+                "⚠️ `{}` is imported from both `{}` and `{}`, using `{}`",
+                import.name, existing.module, import.module, existing.module
+            )),
+            Some(_) => {}
+            None => unique.push(import),
+        }
+    }
+    unique.sort_by(|a, b| (!a.is_builtin(), &a.module, &a.name).cmp(&(!b.is_builtin(), &b.module, &b.name)));
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sort_imports() {
+        let imports = sort_imports([
+            Import::new("webpack", "webpack"),
+            Import::member("VueLoaderPlugin", "vue-loader", "VueLoaderPlugin"),
+            Import::new("HtmlWebpackPlugin", "html-webpack-plugin"),
+            Import::new("webpack", "webpack"),
+            Import::new("path", "path"),
+        ]);
+        let names: Vec<&str> = imports.iter().map(|import| import.name.as_str()).collect();
+
+        assert_eq!(names, ["path", "HtmlWebpackPlugin", "VueLoaderPlugin", "webpack"]);
+    }
+
+    #[test]
+    fn test_rule_to_expr() {
+        let rule = Rule {
+            exclude: Some(Expr::regex("node_modules", "")),
+            loaders: vec![
+                Loader::member(Import::new("MiniCssExtractPlugin", "mini-css-extract-plugin"), "loader"),
+                Loader::with_options("css-loader", json!({ "importLoaders": 1 })),
+            ],
+            ..Rule::new("styles", Expr::regex("\\.css$", ""))
+        };
+        let expected_result = r#"{
+  test: /\.css$/,
+  exclude: /node_modules/,
+  use: [
+    {
+      loader: MiniCssExtractPlugin.loader
+    },
+    {
+      loader: 'css-loader',
+      options: {
+        importLoaders: 1
+      }
+    }
+  ]
+}"#;
+
+        assert_eq!(rule.to_expr().to_js(), expected_result);
+        assert_eq!(rule.imports().count(), 1);
+    }
+
+    #[test]
+    fn test_plugin_to_expr() {
+        let plugin = Plugin::member(Import::new("webpack", "webpack"), "HotModuleReplacementPlugin", vec![]);
+
+        assert_eq!(plugin.to_expr().to_js(), "new webpack.HotModuleReplacementPlugin()");
+    }
+}
@@ -1 +1 @@
-pub mod value_ext;
\ No newline at end of file
+pub mod value_ext;